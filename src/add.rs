@@ -0,0 +1,211 @@
+//! `dolph add`, which applies an integration to an existing project: the config, dependencies
+//! and server bootstrap are updated in place.
+
+use std::fmt;
+use std::fs;
+
+use crate::config::{Api, Database, DefaultConfig, Language, Routing};
+use crate::error::{DolphError, Result};
+use crate::init::confirm_merge;
+use crate::output::message;
+use crate::project::Project;
+use crate::properties::{Generator, Schematic};
+use crate::utils::{write_file, CONFIG_FILE_NAME};
+use crate::writers::{
+    add_dependencies, env_config_file, register_graphql_server, update_datasource_config,
+    update_env_config, update_server_database, write_datasource_config, write_db_config,
+    write_docker_files, write_env_config, write_env_files, write_setup_file, MergePolicy,
+};
+
+/// The integrations `dolph add` can apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integration {
+    Graphql,
+    Mongo,
+    Mysql,
+    Postgres,
+    Socket,
+    Docker,
+}
+
+impl Integration {
+    pub const VARIANTS: &'static [Self] = &[
+        Integration::Graphql,
+        Integration::Mongo,
+        Integration::Mysql,
+        Integration::Postgres,
+        Integration::Socket,
+        Integration::Docker,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Integration::Graphql => "graphql",
+            Integration::Mongo => "mongo",
+            Integration::Mysql => "mysql",
+            Integration::Postgres => "postgres",
+            Integration::Socket => "socket",
+            Integration::Docker => "docker",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|variant| variant.as_str() == value)
+            .copied()
+    }
+}
+
+impl fmt::Display for Integration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Applies `integration` to `project`. `name` names the socket added by [`Integration::Socket`].
+pub async fn run_add(
+    project: &Project,
+    integration: Integration,
+    name: Option<&str>,
+) -> Result<()> {
+    match integration {
+        Integration::Graphql => add_graphql(project),
+        Integration::Mongo => add_database(project, Database::MongoDB),
+        Integration::Mysql => add_database(project, Database::MySQL),
+        Integration::Postgres => add_database(project, Database::PostgreSQL),
+        Integration::Socket => {
+            let config = project.config();
+            if config.api != Api::Rest || config.routing != Routing::Spring {
+                return Err(DolphError::Usage(String::from(
+                    "sockets need rest api and spring routing",
+                )));
+            }
+            report_dependencies(add_dependencies(project)?);
+            Generator::new(project.clone())
+                .generate(Schematic::Socket, name.unwrap_or("app"))
+                .await
+        }
+        Integration::Docker => {
+            write_docker_files(
+                project,
+                &package_name(project),
+                MergePolicy::Confirm(&confirm_merge),
+            )?;
+            message("Added Dockerfile, .dockerignore and docker-compose-dev.yml");
+            Ok(())
+        }
+    }
+}
+
+fn add_database(project: &Project, database: Database) -> Result<()> {
+    let previous = project.config().database;
+    if previous == database {
+        message(format!("The project already uses {}", database));
+        report_dependencies(add_dependencies(project)?);
+        return Ok(());
+    }
+
+    let project = save_config(project, |config| config.database = database)?;
+    let config = project.config();
+
+    write_env_files(&project, &package_name(&project))?;
+    if !update_env_config(&project, previous)? {
+        message(format!(
+            "src/{} was edited, update its database settings for {} by hand",
+            env_config_file(config.language),
+            database
+        ));
+    }
+
+    if config.serves(Api::Graphql) && !update_datasource_config(&project, previous)? {
+        message(format!(
+            "src/shared/configs/data_source.ts was edited, update it for {} by hand",
+            database
+        ));
+    }
+    if config.api == Api::Rest && config.routing == Routing::Spring {
+        if database == Database::MySQL {
+            write_db_config(&project)?;
+        }
+        update_server_database(&project)?;
+    }
+
+    report_dependencies(add_dependencies(&project)?);
+    message(format!("Switched the database to {}", database));
+
+    if project.root().join("docker-compose-dev.yml").exists() {
+        message("Run `dolph add docker` to update the database service in docker-compose-dev.yml");
+    }
+    Ok(())
+}
+
+/// Makes a rest project serve graphql as well, graphql is served with spring routing and
+/// typescript only. The graphql setup and data source are added next to the components, and the
+/// server serves the schema once the data source is initialized.
+fn add_graphql(project: &Project) -> Result<()> {
+    let config = project.config();
+    if config.serves(Api::Graphql) {
+        message("The project already serves graphql");
+        report_dependencies(add_dependencies(project)?);
+        return Ok(());
+    }
+    if config.routing != Routing::Spring || config.language != Language::Ts {
+        return Err(DolphError::Usage(String::from(
+            "graphql needs spring routing and typescript",
+        )));
+    }
+
+    let project = save_config(project, |config| config.graphql = true)?;
+    let src = project.src_dir()?;
+
+    write_env_config(&project)?;
+    if !src.join("shared/configs/data_source.ts").exists() {
+        write_datasource_config(&project)?;
+    }
+    if !src.join("setup.ts").exists() {
+        write_setup_file(&project)?;
+    }
+    register_graphql_server(&project)?;
+
+    report_dependencies(add_dependencies(&project)?);
+    message("Added graphql, generate resolvers with `dolph generate --resolver <NAME>`");
+    Ok(())
+}
+
+/// Writes the config changed by `change` to `dolph_cli.yaml`, and returns the project with it.
+fn save_config(project: &Project, change: impl FnOnce(&mut DefaultConfig)) -> Result<Project> {
+    let mut config = project.config().clone();
+    change(&mut config);
+
+    write_file(
+        project.changes(),
+        &project.root().join(CONFIG_FILE_NAME),
+        config.to_yaml()?,
+    )?;
+    Ok(project.with_config(config))
+}
+
+/// The name in package.json, or the name of the project directory without one.
+fn package_name(project: &Project) -> String {
+    fs::read_to_string(project.root().join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|manifest| manifest["name"].as_str().map(String::from))
+        .or_else(|| {
+            project
+                .root()
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| String::from("app"))
+}
+
+fn report_dependencies(added: Vec<&str>) {
+    if !added.is_empty() {
+        message(format!(
+            "Added to package.json: {}, run your package manager to install them",
+            added.join(", ")
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+//! The clap definitions of the `dolph` subcommands, the binary maps them onto the library API.
+
+use clap::{arg, ArgMatches, Command};
+
+use dolph::add::Integration;
+use dolph::error::{DolphError, Result};
+use dolph::package_manager::PackageManager;
+use dolph::wizard::{is_interactive, run_generate_wizard};
+use dolph::{Generator, ResourceName, Schematic};
+
+pub fn init_command() -> Command<'static> {
+    Command::new("new")
+        .alias("nw")
+        .about("nw")
+        .about("Creates a new dolphjs app")
+        .arg(arg!(<PROJECT_NAME> "The name of the project to create"))
+        .arg(
+            arg!(--"package-manager" <NAME>)
+                .help("Installs the dependencies with this package manager instead of the one dolph was run with")
+                .possible_values(PackageManager::VARIANTS.iter().map(PackageManager::as_str))
+                .required(false),
+        )
+        .arg(
+            arg!(--"skip-install")
+                .help("Does not install the dependencies")
+                .required(false),
+        )
+        .arg(
+            arg!(--"skip-git")
+                .help("Does not create a git repository")
+                .required(false),
+        )
+}
+
+pub fn init_architecture() -> Command<'static> {
+    Command::new("generate")
+        .about("DolphJS CLI")
+        .arg(
+            arg!(-s --service <NAME>)
+                .help("Generates a dolphjs service file")
+                .required(false),
+        )
+        .arg(
+            arg!(-c --controller <NAME>)
+                .help("Generates a dolphjs controller file")
+                .required(false),
+        )
+        .arg(
+            arg!(-r --route <NAME>)
+                .help("Generates a dolphjs routes file")
+                .required(false),
+        )
+        .arg(
+            arg!(-m --model <NAME>)
+                .help("Generates a dolphjs models file")
+                .required(false),
+        )
+        .arg(
+            arg!(-e --entity <NAME>)
+                .help("Generates a dolphjs entity file")
+                .required(false),
+        )
+        .arg(
+            arg!(-i --input <NAME>)
+                .help("Generates a dolphjs input file")
+                .required(false),
+        )
+        .arg(
+            arg!(-d --dto <NAME>)
+                .help("Generates a dolphjs dto file")
+                .required(false),
+        )
+        .arg(
+            arg!(-v --resolver <NAME>)
+                .help("Generates a dolphjs resolver file")
+                .required(false),
+        )
+        .arg(
+            arg!(-y --component <NAME>)
+                .help("Generates a dolphjs spring component file")
+                .required(false),
+        )
+        .arg(
+            arg!(-k --socket <NAME>)
+                .help("Generate a dolphjs socket service and component")
+                .required(false),
+        )
+        .arg(
+            arg!(--middleware <NAME>)
+                .help("Generates a dolphjs middleware file")
+                .required(false),
+        )
+        .arg(
+            arg!(--shield <NAME>)
+                .help("Generates a dolphjs shield file, for spring routing")
+                .required(false),
+        )
+        .arg(
+            arg!(--decorator <NAME>)
+                .help("Generates a decorator into the shared decorators")
+                .required(false),
+        )
+        .arg(
+            arg!(--helper <NAME>)
+                .help("Generates a helper function into the shared helpers")
+                .required(false),
+        )
+        .arg(
+            arg!(--constant <NAME>)
+                .help("Generates a constants file into the shared constants")
+                .required(false),
+        )
+        .arg(
+            arg!(--interface <NAME>)
+                .help("Generates an interface into the shared interfaces, with `--fields` as its properties")
+                .required(false),
+        )
+        .arg(
+            arg!(--validation <NAME>)
+                .help("Generates a class-validator decorator into the shared validations")
+                .required(false),
+        )
+        .arg(
+            arg!(-a --all <NAME>)
+                .help("Generates all dolphjs files for the named parameter")
+                .required(false),
+        )
+        .arg(
+            arg!(--crud)
+                .help("Generates create, read, update and delete endpoints, resolvers and service methods")
+                .required(false),
+        )
+        .arg(
+            arg!(--fields <FIELDS>)
+                .help("Fields of the generated model, entity, dto, input and interface, e.g. `title:string,price:number`")
+                .required(false),
+        )
+        .arg(
+            arg!(--"apply-to" <CONTROLLER>)
+                .help("Applies the middleware or shield to a controller, or to one of its handlers with `<controller>:<handler>`")
+                .required(false),
+        )
+        .arg(
+            arg!(--register)
+                .help("Registers the controller and service in their component, and the component in the server")
+                .required(false),
+        )
+}
+
+pub async fn run_init_architecture(generator: &Generator, matches: &ArgMatches) -> Result<()> {
+    let requested: Vec<(Schematic, &str)> = Schematic::VARIANTS
+        .iter()
+        .filter_map(|schematic| Some((*schematic, matches.value_of(schematic.flag())?)))
+        .collect();
+
+    if requested.is_empty() {
+        if is_interactive() {
+            return run_generate_wizard(generator).await;
+        }
+        return Err(DolphError::Usage(String::from(
+            "nothing to generate, pass a schematic such as `--controller <NAME>` or run `dolph generate` in a terminal",
+        )));
+    }
+
+    // Validate every name up front so an invalid one does not leave half the files generated
+    for (schematic, name) in &requested {
+        if schematic.is_shared() {
+            ResourceName::parse(name)?;
+        } else {
+            generator.resource(name)?;
+        }
+    }
+    generator.apply_target()?;
+
+    for (schematic, name) in requested {
+        generator.generate(schematic, name).await?;
+    }
+
+    Ok(())
+}
+
+pub fn watch_command() -> Command<'static> {
+    Command::new("watch").about("Watch dolph.js server").arg(
+        arg!(-b - -bun)
+            .help("Uses bun as runtime to run the Dolph app")
+            .required(false),
+    )
+}
+
+pub fn start_command() -> Command<'static> {
+    Command::new("start").about("Start dolph.js server").arg(
+        arg!(-b - -bun)
+            .help("Uses bun as runtime to run the Dolph app")
+            .required(false),
+    )
+}
+
+pub fn build_command() -> Command<'static> {
+    Command::new("build").about("Build dolph.js ts project to js project for production")
+}
+
+pub fn doctor_command() -> Command<'static> {
+    Command::new("doctor").about("Checks your environment and dolph project for common problems")
+}
+
+pub fn add_command() -> Command<'static> {
+    Command::new("add")
+        .about("Adds an integration to an existing dolph project")
+        .arg(
+            arg!(<INTEGRATION> "The integration to add")
+                .possible_values(Integration::VARIANTS.iter().map(Integration::as_str)),
+        )
+        .arg(arg!([NAME] "The name of the socket to generate, for `socket`"))
+}
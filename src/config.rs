@@ -0,0 +1,621 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// The `dolph_cli.yaml` schema version written by this CLI.
+pub const CONFIG_VERSION: u32 = 2;
+
+pub const CONFIG_HEADER: &str = "# this is an auto-generated file, please do not edit manually";
+
+const CONFIG_KEYS: &[&str] = &[
+    "version", "language", "database", "routing", "api", "naming", "graphql",
+];
+
+/// The optional key set on rest projects that serve graphql next to their components.
+const GRAPHQL_KEY: &str = "graphql";
+
+/// A value that can be set for one of the `dolph_cli.yaml` keys.
+pub trait ConfigValue: Sized + Copy + 'static {
+    const KEY: &'static str;
+    const VARIANTS: &'static [Self];
+
+    fn as_str(&self) -> &'static str;
+
+    fn allowed() -> Vec<&'static str> {
+        Self::VARIANTS
+            .iter()
+            .map(|variant| variant.as_str())
+            .collect()
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|variant| variant.as_str() == value)
+            .copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Api {
+    Rest,
+    Graphql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Routing {
+    Express,
+    Spring,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Ts,
+    Js,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Database {
+    #[serde(rename = "mongo")]
+    MongoDB,
+    #[serde(rename = "mysql")]
+    MySQL,
+    #[serde(rename = "postgresql")]
+    PostgreSQL,
+    #[serde(rename = "other")]
+    Other,
+}
+
+/// Whether routes, tables and collections use the plural or the singular of a resource name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Naming {
+    Plural,
+    Singular,
+}
+
+impl ConfigValue for Api {
+    const KEY: &'static str = "api";
+    const VARIANTS: &'static [Self] = &[Api::Graphql, Api::Rest];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Api::Rest => "rest",
+            Api::Graphql => "graphql",
+        }
+    }
+}
+
+impl ConfigValue for Routing {
+    const KEY: &'static str = "routing";
+    const VARIANTS: &'static [Self] = &[Routing::Express, Routing::Spring];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Routing::Express => "express",
+            Routing::Spring => "spring",
+        }
+    }
+}
+
+impl ConfigValue for Language {
+    const KEY: &'static str = "language";
+    const VARIANTS: &'static [Self] = &[Language::Ts, Language::Js];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Language::Ts => "ts",
+            Language::Js => "js",
+        }
+    }
+}
+
+impl Language {
+    /// The server entry below `src/`, which `dolph watch` runs.
+    pub fn server_file(&self) -> &'static str {
+        match self {
+            Language::Ts => "server.ts",
+            Language::Js => "server.js",
+        }
+    }
+
+    /// The file node runs in production, relative to the project root. Typescript projects run
+    /// what `dolph build` compiles the server entry to.
+    pub fn entry_file(&self) -> &'static str {
+        match self {
+            Language::Ts => "app/src/server.js",
+            Language::Js => "src/server.js",
+        }
+    }
+}
+
+impl ConfigValue for Database {
+    const KEY: &'static str = "database";
+    const VARIANTS: &'static [Self] = &[
+        Database::MongoDB,
+        Database::MySQL,
+        Database::PostgreSQL,
+        Database::Other,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Database::MongoDB => "mongo",
+            Database::MySQL => "mysql",
+            Database::PostgreSQL => "postgresql",
+            Database::Other => "other",
+        }
+    }
+}
+
+impl ConfigValue for Naming {
+    const KEY: &'static str = "naming";
+    const VARIANTS: &'static [Self] = &[Naming::Plural, Naming::Singular];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Naming::Plural => "plural",
+            Naming::Singular => "singular",
+        }
+    }
+}
+
+impl fmt::Display for Api {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Routing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Database {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Naming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DefaultConfig {
+    pub version: u32,
+    pub language: Language,
+    pub database: Database,
+    pub routing: Routing,
+    pub api: Api,
+    pub naming: Naming,
+    /// Whether a rest project also serves graphql, as `dolph add graphql` makes it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub graphql: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl DefaultConfig {
+    pub fn new(api: Api, routing: Routing, language: Language, database: Database) -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            language,
+            database,
+            routing,
+            api,
+            naming: Naming::Plural,
+            graphql: false,
+        }
+    }
+
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Whether the project serves `api`, rest projects may serve graphql as well.
+    pub fn serves(&self, api: Api) -> bool {
+        self.api == api || (api == Api::Graphql && self.graphql)
+    }
+
+    /// The server entry below `src/` that `dolph new` writes. Express projects bring their own.
+    pub fn server_file(&self) -> Option<&'static str> {
+        (self.routing == Routing::Spring).then(|| self.language.server_file())
+    }
+
+    /// Whether `--crud` has a database layer to generate against: mongoose and sequelize models
+    /// in rest projects, the typeorm repository in graphql ones.
+    pub fn supports_crud(&self) -> bool {
+        self.api == Api::Graphql || matches!(self.database, Database::MongoDB | Database::MySQL)
+    }
+
+    /// Renders the config the way it is stored in `dolph_cli.yaml`.
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        Ok(format!(
+            "{}\n{}",
+            CONFIG_HEADER,
+            serde_yaml::to_string(self)?
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    NotFound {
+        start: PathBuf,
+    },
+    InvalidRoot {
+        root: PathBuf,
+    },
+    Unreadable(io::Error),
+    Syntax {
+        line: Option<usize>,
+        message: String,
+    },
+    MissingKey {
+        key: &'static str,
+    },
+    UnknownKey {
+        line: Option<usize>,
+        key: String,
+    },
+    InvalidValue {
+        line: Option<usize>,
+        key: &'static str,
+        value: String,
+        allowed: Vec<String>,
+    },
+    UnsupportedVersion {
+        line: Option<usize>,
+        version: u64,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let at = |line: &Option<usize>| match line {
+            Some(line) => format!("dolph_cli.yaml:{}", line),
+            None => String::from("dolph_cli.yaml"),
+        };
+
+        match self {
+            ConfigError::NotFound { start } => write!(
+                f,
+                "Could not find dolph_cli.yaml in {} or any of its parent directories",
+                start.display()
+            ),
+            ConfigError::InvalidRoot { root } => write!(
+                f,
+                "DOLPH_PROJECT_ROOT is set to {}, but it has no dolph_cli.yaml",
+                root.display()
+            ),
+            ConfigError::Unreadable(e) => write!(f, "dolph_cli.yaml could not be read: {}", e),
+            ConfigError::Syntax { line, message } => {
+                write!(f, "{}: invalid yaml: {}", at(line), message)
+            }
+            ConfigError::MissingKey { key } => {
+                write!(f, "dolph_cli.yaml: missing required key `{}`", key)
+            }
+            ConfigError::UnknownKey { line, key } => write!(
+                f,
+                "{}: unknown key `{}`, expected one of: {}",
+                at(line),
+                key,
+                CONFIG_KEYS.join(", ")
+            ),
+            ConfigError::InvalidValue {
+                line,
+                key,
+                value,
+                allowed,
+            } => write!(
+                f,
+                "{}: invalid value `{}` for `{}`, expected one of: {}",
+                at(line),
+                value,
+                key,
+                allowed.join(", ")
+            ),
+            ConfigError::UnsupportedVersion { line, version } => write!(
+                f,
+                "{}: config version {} is newer than this CLI supports ({}), please upgrade dolph",
+                at(line),
+                version,
+                CONFIG_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Parses the contents of `dolph_cli.yaml`.
+///
+/// Older config files are migrated to [`CONFIG_VERSION`]; the returned flag tells the caller
+/// whether that happened, so the file can be rewritten.
+pub fn parse_config(content: &str) -> Result<(DefaultConfig, bool), ConfigError> {
+    let document: Value = serde_yaml::from_str(content).map_err(|e| ConfigError::Syntax {
+        line: e.location().map(|location| location.line()),
+        message: e.to_string(),
+    })?;
+
+    let mapping = match document {
+        Value::Mapping(mapping) => mapping,
+        _ => {
+            return Err(ConfigError::Syntax {
+                line: None,
+                message: String::from("expected a mapping of config keys"),
+            })
+        }
+    };
+
+    for key in mapping.keys() {
+        let key = key.as_str().unwrap_or_default();
+        if !CONFIG_KEYS.contains(&key) {
+            return Err(ConfigError::UnknownKey {
+                line: find_line(content, key),
+                key: key.to_string(),
+            });
+        }
+    }
+
+    let version = match mapping.get("version") {
+        None => 0,
+        Some(value) => match value.as_u64() {
+            Some(version) if version > CONFIG_VERSION as u64 => {
+                return Err(ConfigError::UnsupportedVersion {
+                    line: find_line(content, "version"),
+                    version,
+                })
+            }
+            Some(version) => version as u32,
+            None => {
+                return Err(ConfigError::InvalidValue {
+                    line: find_line(content, "version"),
+                    key: "version",
+                    value: scalar_to_string(value),
+                    allowed: (1..=CONFIG_VERSION).map(|v| v.to_string()).collect(),
+                })
+            }
+        },
+    };
+
+    let mapping = migrate(mapping, version);
+
+    let config = DefaultConfig {
+        version: CONFIG_VERSION,
+        language: field(&mapping, content)?,
+        database: field(&mapping, content)?,
+        routing: field(&mapping, content)?,
+        api: field(&mapping, content)?,
+        naming: field(&mapping, content)?,
+        graphql: match mapping.get(GRAPHQL_KEY) {
+            None => false,
+            Some(Value::Bool(graphql)) => *graphql,
+            Some(value) => {
+                return Err(ConfigError::InvalidValue {
+                    line: find_line(content, GRAPHQL_KEY),
+                    key: GRAPHQL_KEY,
+                    value: scalar_to_string(value),
+                    allowed: vec![String::from("true"), String::from("false")],
+                })
+            }
+        },
+    };
+
+    if config.serves(Api::Graphql) && config.routing != Routing::Spring {
+        return Err(ConfigError::InvalidValue {
+            line: find_line(content, Routing::KEY),
+            key: Routing::KEY,
+            value: config.routing.to_string(),
+            allowed: vec![Routing::Spring.to_string()],
+        });
+    }
+
+    Ok((config, version < CONFIG_VERSION))
+}
+
+/// Brings a config mapping written by an older CLI up to the current schema.
+fn migrate(mut mapping: serde_yaml::Mapping, version: u32) -> serde_yaml::Mapping {
+    if version < 1 {
+        // Unversioned files were written before the values were validated, so they may use
+        // different casing or the short `postgres` name used by the datasource config.
+        for key in [Api::KEY, Routing::KEY, Language::KEY, Database::KEY] {
+            if let Some(Value::String(value)) = mapping.get_mut(key) {
+                *value = value.trim().to_lowercase();
+                if key == Database::KEY && value == "postgres" {
+                    *value = String::from("postgresql");
+                }
+            }
+        }
+    }
+
+    if version < 2 {
+        // Routes were always pluralized before naming became configurable
+        mapping
+            .entry(Value::from(Naming::KEY))
+            .or_insert_with(|| Value::from(Naming::Plural.as_str()));
+    }
+
+    mapping
+}
+
+fn field<T: ConfigValue>(mapping: &serde_yaml::Mapping, content: &str) -> Result<T, ConfigError> {
+    let value = mapping
+        .get(T::KEY)
+        .ok_or(ConfigError::MissingKey { key: T::KEY })?;
+
+    value
+        .as_str()
+        .and_then(T::parse)
+        .ok_or_else(|| ConfigError::InvalidValue {
+            line: find_line(content, T::KEY),
+            key: T::KEY,
+            value: scalar_to_string(value),
+            allowed: T::allowed().into_iter().map(String::from).collect(),
+        })
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn find_line(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            line.trim_start()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(content: &str) -> String {
+        parse_config(content).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn current_configs_round_trip() {
+        let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MySQL)
+            .with_naming(Naming::Singular);
+
+        let (parsed, migrated) = parse_config(&config.to_yaml().unwrap()).unwrap();
+
+        assert_eq!(parsed, config);
+        assert!(!migrated);
+    }
+
+    #[test]
+    fn unversioned_configs_are_migrated() {
+        let content = "# an old dolph_cli.yaml\napi: REST\nrouting: Spring\nlanguage: TS\ndatabase: postgres\n";
+
+        let (config, migrated) = parse_config(content).unwrap();
+
+        assert!(migrated);
+        assert_eq!(
+            config,
+            DefaultConfig::new(
+                Api::Rest,
+                Routing::Spring,
+                Language::Ts,
+                Database::PostgreSQL
+            )
+        );
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.naming, Naming::Plural);
+
+        // The migrated file is read back as it is
+        let (reparsed, migrated) = parse_config(&config.to_yaml().unwrap()).unwrap();
+        assert_eq!(reparsed, config);
+        assert!(!migrated);
+    }
+
+    #[test]
+    fn version_one_configs_get_plural_naming() {
+        let content = "version: 1\napi: graphql\nrouting: spring\nlanguage: ts\ndatabase: mongo\n";
+
+        let (config, migrated) = parse_config(content).unwrap();
+
+        assert!(migrated);
+        assert_eq!(config.naming, Naming::Plural);
+        assert_eq!(config.database, Database::MongoDB);
+    }
+
+    #[test]
+    fn rest_projects_can_serve_graphql() {
+        let mut config =
+            DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MySQL);
+        assert!(!config.to_yaml().unwrap().contains("graphql"));
+
+        config.graphql = true;
+        let (parsed, migrated) = parse_config(&config.to_yaml().unwrap()).unwrap();
+        assert_eq!(parsed, config);
+        assert!(!migrated);
+        assert!(parsed.serves(Api::Rest) && parsed.serves(Api::Graphql));
+
+        assert_eq!(
+            parse_error("version: 2\napi: rest\nrouting: express\nlanguage: ts\ndatabase: mysql\nnaming: plural\ngraphql: true\n"),
+            "dolph_cli.yaml:3: invalid value `express` for `routing`, expected one of: spring"
+        );
+        assert_eq!(
+            parse_error("version: 2\napi: rest\nrouting: spring\nlanguage: ts\ndatabase: mysql\nnaming: plural\ngraphql: yes\n"),
+            "dolph_cli.yaml:7: invalid value `yes` for `graphql`, expected one of: true, false"
+        );
+    }
+
+    #[test]
+    fn errors_point_to_the_offending_line() {
+        let content =
+            "# header\nversion: 2\napi: rest\nrouting: sprng\nlanguage: ts\ndatabase: mongo\n";
+        assert_eq!(
+            parse_error(content),
+            "dolph_cli.yaml:4: invalid value `sprng` for `routing`, expected one of: express, spring"
+        );
+
+        assert_eq!(
+            parse_error("version: 2\napi: rest\ncolour: blue\n"),
+            "dolph_cli.yaml:3: unknown key `colour`, expected one of: version, language, database, routing, api, naming, graphql"
+        );
+        assert_eq!(
+            parse_error("api: rest\nrouting: spring\nlanguage: ts\n"),
+            "dolph_cli.yaml: missing required key `database`"
+        );
+        assert_eq!(
+            find_line("api: rest\n  routing : spring\n", "routing"),
+            Some(2)
+        );
+        assert_eq!(
+            find_line("api: rest\nrouting_style: spring\n", "routing"),
+            None
+        );
+    }
+
+    #[test]
+    fn versions_are_checked_against_the_current_one() {
+        assert_eq!(
+            parse_error(&format!("version: {}\n", CONFIG_VERSION + 1)),
+            format!(
+                "dolph_cli.yaml:1: config version {} is newer than this CLI supports ({}), please upgrade dolph",
+                CONFIG_VERSION + 1,
+                CONFIG_VERSION
+            )
+        );
+
+        let allowed: Vec<String> = (1..=CONFIG_VERSION).map(|v| v.to_string()).collect();
+        assert_eq!(
+            parse_error("version: latest\n"),
+            format!(
+                "dolph_cli.yaml:1: invalid value `latest` for `version`, expected one of: {}",
+                allowed.join(", ")
+            )
+        );
+    }
+}
@@ -1,37 +1,77 @@
-use std::error::Error;
+use std::io::ErrorKind;
+use std::process::Stdio;
+use std::time::Instant;
 use tokio::process::Command as AsyncCommand;
 
-pub async fn build_ts_app(language: &str) -> Result<(), Box<dyn Error>> {
-    if language.to_string() != "ts" {
-        println!(
-            "{} {}",
-            "[Dolph Error]: ", "Cannot compile javascript file, exciting compilation..."
-        );
-        return Err("Cannot compile Javascript file".into());
+use crate::config::Language;
+use crate::error::{DolphError, Result};
+use crate::output::{event, is_json, message, Diagnostic, Event};
+
+pub async fn build_ts_app(language: Language) -> Result<()> {
+    if language != Language::Ts {
+        return Err(DolphError::Build(String::from(
+            "Cannot compile a javascript project, only typescript projects need to be built",
+        )));
     }
 
-    println!(
-        "{} {}",
-        "[Dolph Info]: ", "Compiling from Typescript to Javascript..."
-    );
+    message("[Dolph Info]:  Compiling from Typescript to Javascript...");
 
     let spawn_args = vec!["src", "-d", "app", "--source-maps", "--copy-files"];
 
-    let mut child = AsyncCommand::new("swc").args(&spawn_args).spawn()?;
-
-    match child.wait().await {
-        Ok(status) => {
-            if !status.success() {
-                println!("{} {}", "[Dolph Error]: ", "Exciting compilation...");
-                return Err("Compilation error...".into());
-            }
-
-            println!("{} {}", "[Dolph Info]: ", "Compilation successful");
-            Ok(())
-        }
-        Err(e) => {
-            println!("{} {}", "[Dolph Error]: ", format!("{}", e));
-            return Err(e.into());
-        }
+    let started = Instant::now();
+
+    // swc's own output is turned into diagnostics in JSON mode, and shown as-is otherwise
+    let (stdout, stderr) = if is_json() {
+        (Stdio::piped(), Stdio::piped())
+    } else {
+        (Stdio::inherit(), Stdio::inherit())
+    };
+
+    let output = AsyncCommand::new("swc")
+        .args(&spawn_args)
+        .stdout(stdout)
+        .stderr(stderr)
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => DolphError::RuntimeNotFound(String::from("swc")),
+            _ => DolphError::Build(format!("Could not run swc: {}", e)),
+        })?;
+
+    let duration = started.elapsed();
+    let success = output.status.success();
+
+    event(&Event::Compile {
+        success,
+        duration_ms: duration.as_millis(),
+        diagnostics: diagnostics(&String::from_utf8_lossy(&output.stderr), success),
+    });
+
+    if !success {
+        return Err(DolphError::Build(format!(
+            "Compilation failed, swc exited with {}",
+            output.status
+        )));
     }
+
+    message(format!(
+        "[Dolph Info]:  Compilation successful in {:.2}s",
+        duration.as_secs_f64()
+    ));
+    Ok(())
+}
+
+/// Splits swc's stderr into diagnostics, which are separated by blank lines.
+fn diagnostics(stderr: &str, success: bool) -> Vec<Diagnostic> {
+    let severity = if success { "warning" } else { "error" };
+
+    stderr
+        .split("\n\n")
+        .map(str::trim_end)
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| Diagnostic {
+            severity,
+            message: block.to_string(),
+        })
+        .collect()
 }
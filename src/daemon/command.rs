@@ -1,77 +1,59 @@
-use clap::{arg, ArgMatches, Command};
-
-use crate::{daemon::runner, utils::is_bin_installed};
+use crate::{
+    config::Language,
+    daemon::runner,
+    error::{DolphError, Result},
+    output::message,
+    utils::is_bin_installed,
+};
 
 use super::watcher;
 
-pub fn init_watch_command(language: &str, matches: &ArgMatches) -> () {
-    let use_bun = matches.is_present("bun");
-
+/// Runs the server in development mode and restarts it whenever a source file changes.
+pub fn run_watch(language: Language, use_bun: bool) -> Result<()> {
     if use_bun {
-        println!("Using Bun...");
-        println!("Checking if system has bun installed...");
+        message("Using Bun...");
+        message("Checking if system has bun installed...");
         if is_bin_installed("bun") {
-            println!("Bun is installed. Using Bun...");
-            watcher("development", "8080", language, true);
+            message("Bun is installed. Using Bun...");
+            watcher("development", "8080", language, true)
         } else {
-            println!("Bun is not installed. Falling back to Node.js...");
-            watcher("development", "8080", language, false);
+            message("Bun is not installed. Falling back to Node.js...");
+            watcher("development", "8080", language, false)
         }
     } else {
-        println!("Using Node...");
-        println!("Checking if system has node.js installed...");
+        message("Using Node...");
+        message("Checking if system has node.js installed...");
 
         if is_bin_installed("node") {
-            println!("Node is installed. Using Node...");
-            watcher("development", "8080", language, false);
+            message("Node is installed. Using Node...");
+            watcher("development", "8080", language, false)
         } else {
-            println!("Node is not installed. Quitting...")
+            Err(DolphError::RuntimeNotFound(String::from("node")))
         }
     }
 }
 
-pub fn init_start_command(language: &str, matches: &ArgMatches) -> () {
-    let use_bun = matches.is_present("bun");
-
+/// Runs the server once, until it exits.
+pub fn run_start(language: Language, use_bun: bool) -> Result<()> {
     if use_bun {
-        println!("Using Bun...");
-        println!("Checking if system has bun installed...");
+        message("Using Bun...");
+        message("Checking if system has bun installed...");
         if is_bin_installed("bun") {
-            println!("Bun is installed. Using Bun...");
-            runner("development", "8080", language, true);
+            message("Bun is installed. Using Bun...");
+            runner("development", "8080", language, true)
         } else {
-            println!("Bun is not installed. Falling back to Node.js...");
-            runner("development", "8080", language, false);
+            message("Bun is not installed. Falling back to Node.js...");
+            runner("development", "8080", language, false)
         }
     } else {
-        println!("Using Node...");
-        println!("Checking if system has node.js installed...");
+        message("Using Node...");
+        message("Checking if system has node.js installed...");
 
         if is_bin_installed("node") {
-            println!("Node is installed. Using Node...");
-            runner("development", "8080", language, false);
+            message("Node is installed. Using Node...");
+            runner("development", "8080", language, false)
         } else {
-            println!("Node is not installed. Quitting...")
+            Err(DolphError::RuntimeNotFound(String::from("node")))
         }
     }
 }
-
-pub fn watch_command() -> Command<'static> {
-    Command::new("watch").about("Watch dolph.js server").arg(
-        arg!(-b - -bun)
-            .help("Uses bun as runtime to run the Dolph app")
-            .required(false),
-    )
-}
-
-pub fn start_command() -> Command<'static> {
-    Command::new("start").about("Start dolph.js server").arg(
-        arg!(-b - -bun)
-            .help("Uses bun as runtime to run the Dolph app")
-            .required(false),
-    )
-}
-
-pub fn build_command() -> Command<'static> {
-    Command::new("build").about("Build dolph.js ts project to js project for production")
-}
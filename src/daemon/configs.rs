@@ -1,10 +1,13 @@
 use std::{
+    io,
     path::PathBuf,
-    process::{Child, Command},
+    process::{Child, Command, ExitStatus, Stdio},
 };
 
 use slog::Logger;
 
+use crate::output::is_json;
+
 #[derive(Debug, Clone)]
 pub struct CommandConfig {
     pub program: String,
@@ -35,6 +38,7 @@ pub struct Config {
 }
 
 impl CommandConfig {
+    #[allow(dead_code)]
     pub fn new(program: &str) -> Self {
         CommandConfig {
             program: program.to_string(),
@@ -53,7 +57,7 @@ impl ServerProcess {
         }
     }
 
-    pub fn start(&mut self, config: &CommandConfig) {
+    pub fn start(&mut self, config: &CommandConfig) -> io::Result<()> {
         self.stop();
 
         slog::info!(self.logger, "Starting server: {}", config.program);
@@ -69,17 +73,31 @@ impl ServerProcess {
             command.env(key, value);
         }
 
+        // Keep stdout free for JSON events, the server's own logs go to stderr instead
+        if is_json() {
+            command.stdout(Stdio::from(io::stderr()));
+        }
+
         match command.spawn() {
             Ok(child) => {
                 self.child = Some(child);
                 slog::info!(self.logger, "Server started successfully");
+                Ok(())
             }
             Err(e) => {
                 slog::error!(self.logger, "Failed to start the server: {}", e);
+                Err(e)
             }
         }
     }
 
+    /// Returns the exit status if the server exited on its own since the last check.
+    pub fn try_exit(&mut self) -> Option<ExitStatus> {
+        let status = self.child.as_mut()?.try_wait().ok()??;
+        self.child = None;
+        Some(status)
+    }
+
     pub fn stop(&mut self) {
         if let Some(mut child) = self.child.take() {
             slog::info!(self.logger, "Stopping server ...");
@@ -6,8 +6,6 @@ mod runner;
 mod watcher;
 
 pub use builder::build_ts_app;
-pub use command::{
-    build_command, init_start_command, init_watch_command, start_command, watch_command,
-};
+pub use command::{run_start, run_watch};
 pub use runner::runner;
 pub use watcher::watcher;
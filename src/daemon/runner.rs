@@ -1,18 +1,24 @@
-use slog;
-use std::{path::PathBuf, process::Command};
+use std::{
+    io::{self, ErrorKind},
+    process::{Command, Stdio},
+};
 
+use crate::config::Language;
 use crate::daemon::watcher::setup_logger;
+use crate::error::{DolphError, Result};
+use crate::output::{self, is_json, timestamp, Event};
 
-pub fn runner(env: &str, port: &str, language: &str, bun: bool) {
+pub fn runner(env: &str, port: &str, language: Language, bun: bool) -> Result<()> {
     let logger = setup_logger();
     slog::info!(logger, "Starting server");
 
+    let entry = language.entry_file();
     let (bin, args) = if bun {
-        ("bun", vec!["run", "./app/src/server.js"])
-    } else if language == "ts" {
-        ("ts-node", vec!["./app/src/server.js"])
+        ("bun", vec!["run", entry])
+    } else if language == Language::Ts {
+        ("ts-node", vec![entry])
     } else {
-        ("node", vec!["./app/src/server.js"])
+        ("node", vec![entry])
     };
 
     let mut command = Command::new(bin);
@@ -22,16 +28,38 @@ pub fn runner(env: &str, port: &str, language: &str, bun: bool) {
         .env("NODE_ENV", env)
         .env("PORT", port);
 
-    match command.status() {
-        Ok(status) => {
-            if status.success() {
-                slog::info!(logger, "Server started successfully");
-            } else {
-                slog::error!(logger, "Server exited with status: {}", status);
-            }
-        }
-        Err(e) => slog::error!(logger, "Failed to start server: {}", e),
+    if is_json() {
+        command.stdout(Stdio::from(io::stderr()));
     }
 
+    let mut child = command.spawn().map_err(|e| match e.kind() {
+        ErrorKind::NotFound => DolphError::RuntimeNotFound(bin.to_string()),
+        _ => DolphError::Runtime(format!("Failed to start server: {}", e)),
+    })?;
+
+    output::event(&Event::Start {
+        program: bin,
+        timestamp: timestamp(),
+    });
+
+    let status = child.wait()?;
+
     slog::info!(logger, "Server process ended");
+
+    if status.success() {
+        output::event(&Event::Exit {
+            timestamp: timestamp(),
+        });
+    } else {
+        output::event(&Event::Crash {
+            exit_code: status.code(),
+            timestamp: timestamp(),
+        });
+        return Err(DolphError::Runtime(format!(
+            "Server exited with status: {}",
+            status
+        )));
+    }
+
+    Ok(())
 }
@@ -1,4 +1,5 @@
 use std::{
+    io::ErrorKind,
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -17,7 +18,10 @@ use signal_hook::{consts::SIGINT, iterator::Signals};
 
 use slog::{o, Drain, Logger};
 
+use crate::config::Language;
 use crate::daemon::configs::{CommandConfig, Config, ServerProcess, WatchConfig};
+use crate::error::{DolphError, Result};
+use crate::output::{self, timestamp, Event as OutputEvent};
 
 pub fn setup_logger() -> Logger {
     let decorator = slog_term::TermDecorator::new().build();
@@ -42,7 +46,7 @@ fn should_restart_for_event(event: &Event, config: &WatchConfig) -> bool {
     }
 
     for path in &event.paths {
-        if should_ignore(&path, &config.ignore_patterns) {
+        if should_ignore(path, &config.ignore_patterns) {
             continue;
         }
 
@@ -58,29 +62,28 @@ fn should_restart_for_event(event: &Event, config: &WatchConfig) -> bool {
     false
 }
 
-pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
+pub fn watcher(env: &str, port: &str, language: Language, bun: bool) -> Result<()> {
     let logger = setup_logger();
     slog::info!(logger, "Starting dolph server daemon");
 
     let mut bin = "node";
-    let mut server_path = "./src/server.js";
+    let server_path = format!("./src/{}", language.server_file());
 
-    if language.to_string() == "ts" {
+    if language == Language::Ts {
         bin = "ts-node"; // update to ts-node-dev
-        server_path = "./src/server.ts";
     };
 
-    let mut args = vec![String::from(server_path)];
+    let mut args = vec![server_path.clone()];
 
     if bun {
         bin = "bun";
-        args = vec![String::from("run"), String::from(server_path)]
+        args = vec![String::from("run"), server_path]
     }
 
     let config = Config {
         command: CommandConfig {
             program: String::from(bin),
-            args: args,
+            args,
             cwd: Some(PathBuf::from(".")),
             env: vec![
                 ("NODE_ENV".to_string(), env.to_string()),
@@ -116,20 +119,19 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
             slog::info!(logger_clone, "Received Ctrl+c signal");
             running_clone.store(false, Ordering::SeqCst);
         })
-        .expect("Error setting Ctrl+c handler")
+        .map_err(|e| DolphError::Runtime(format!("Error setting Ctrl+c handler: {}", e)))?;
     }
 
     #[cfg(unix)]
     {
         let running_clone = running.clone();
         let logger_signals = logger.clone();
-        let mut signals = Signals::new(&[SIGINT]).unwrap();
+        let mut signals = Signals::new([SIGINT])?;
 
         thread::spawn(move || {
-            for sig in signals.forever() {
+            if let Some(sig) = signals.forever().next() {
                 slog::info!(logger_signals, "Received signal: {}", sig);
                 running_clone.store(false, Ordering::SeqCst);
-                break;
             }
         });
     }
@@ -137,7 +139,15 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
     // Create server process manager
     let mut server = ServerProcess::new(logger.clone());
 
-    server.start(&config.command);
+    server.start(&config.command).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => DolphError::RuntimeNotFound(config.command.program.clone()),
+        _ => DolphError::Runtime(format!("Failed to start the server: {}", e)),
+    })?;
+
+    output::event(&OutputEvent::Start {
+        program: &config.command.program,
+        timestamp: timestamp(),
+    });
 
     // Setup file watcher
     let (tx, rx) = std::sync::mpsc::channel();
@@ -145,10 +155,11 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
     let watcher_config =
         NotifyConfig::default().with_poll_interval(Duration::from_millis(config.watch.debounce_ms));
 
-    let mut watcher: RecommendedWatcher = Watcher::new(tx, watcher_config).unwrap();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, watcher_config)
+        .map_err(|e| DolphError::Runtime(format!("Failed to start the file watcher: {}", e)))?;
 
     for watch_path in &config.watch.paths {
-        match watcher.watch(&watch_path, RecursiveMode::Recursive) {
+        match watcher.watch(watch_path, RecursiveMode::Recursive) {
             Ok(_) => slog::info!(logger, "Watching path {:?} for changes", watch_path),
             Err(e) => slog::error!(logger, "Failed to watch path: {:?} : {}", watch_path, e),
         }
@@ -157,6 +168,25 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
     let config_clone = config.clone();
 
     while running.load(Ordering::SeqCst) {
+        if let Some(status) = server.try_exit() {
+            if status.success() {
+                slog::info!(logger, "Server exited, waiting for changes...");
+                output::event(&OutputEvent::Exit {
+                    timestamp: timestamp(),
+                });
+            } else {
+                slog::error!(
+                    logger,
+                    "Server crashed with {}, waiting for changes...",
+                    status
+                );
+                output::event(&OutputEvent::Crash {
+                    exit_code: status.code(),
+                    timestamp: timestamp(),
+                });
+            }
+        }
+
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(event) => {
                 if let Ok(event) = event {
@@ -168,11 +198,20 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
                             > config_clone.watch.debounce_ms as u128
                         {
                             slog::info!(logger, "Change detected in {:?}", event.paths[0]);
+                            output::event(&OutputEvent::Change {
+                                path: &event.paths[0],
+                                timestamp: timestamp(),
+                            });
 
                             slog::info!(logger, "Restarting server...");
 
                             thread::sleep(Duration::from_millis(100));
-                            server.start(&config_clone.command);
+                            // A failed restart is already logged, the next change retries it
+                            if server.start(&config_clone.command).is_ok() {
+                                output::event(&OutputEvent::Restart {
+                                    timestamp: timestamp(),
+                                });
+                            }
                             *last = now;
                         }
                     }
@@ -185,4 +224,8 @@ pub fn watcher(env: &str, port: &str, language: &str, bun: bool) {
 
     server.stop();
     slog::info!(logger, "Dolph watch daemon stopped");
+    output::event(&OutputEvent::Stop {
+        timestamp: timestamp(),
+    });
+    Ok(())
 }
@@ -0,0 +1,455 @@
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+
+use crate::config::{parse_config, ConfigError, DefaultConfig, Language, Routing};
+use crate::error::{DolphError, Result};
+use crate::output::is_json;
+use crate::utils::{bin_version, resolve_project_root, CONFIG_FILE_NAME};
+
+const DEFAULT_PORT: u16 = 3300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolReport {
+    pub name: String,
+    pub version: Option<String>,
+    pub required: Option<String>,
+    pub status: Status,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    pub name: String,
+    pub status: Status,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub tools: Vec<ToolReport>,
+    pub checks: Vec<CheckReport>,
+}
+
+impl DoctorReport {
+    fn problems(&self) -> usize {
+        self.tools
+            .iter()
+            .map(|tool| tool.status)
+            .chain(self.checks.iter().map(|check| check.status))
+            .filter(|status| *status == Status::Error)
+            .count()
+    }
+}
+
+/// Diagnoses the project found from `cwd`, see [`resolve_project_root`], and prints the report.
+pub fn run_doctor(cwd: Option<&str>) -> Result<()> {
+    // Outside of a project the directory itself gets diagnosed
+    let root = match resolve_project_root(cwd) {
+        Ok(root) => root,
+        Err(_) => match cwd {
+            Some(cwd) => Path::new(cwd).canonicalize()?,
+            None => std::env::current_dir()?,
+        },
+    };
+    let report = diagnose(&root);
+
+    if is_json() {
+        println!("{}", serde_json::to_string(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    match report.problems() {
+        0 => Ok(()),
+        problems => Err(DolphError::Doctor { problems }),
+    }
+}
+
+pub fn diagnose(root: &Path) -> DoctorReport {
+    let package_json = read_json(&root.join("package.json"));
+    let config_path = root.join(CONFIG_FILE_NAME);
+    // Parsed rather than read through `read_config`, so diagnosing never migrates the file on disk.
+    let config = if config_path.exists() {
+        Some(read_config_file(&config_path))
+    } else {
+        None
+    };
+
+    DoctorReport {
+        tools: check_tools(root, package_json.as_ref()),
+        checks: check_project(root, config, package_json.as_ref()),
+    }
+}
+
+fn read_config_file(path: &Path) -> Result<DefaultConfig> {
+    let content = fs::read_to_string(path).map_err(ConfigError::Unreadable)?;
+    let (config, _) = parse_config(&content)?;
+    Ok(config)
+}
+
+fn check_tools(root: &Path, package_json: Option<&Value>) -> Vec<ToolReport> {
+    let engines = package_json.and_then(|package| package.get("engines"));
+
+    ["node", "bun", "yarn", "npm", "swc", "tsc", "ts-node"]
+        .iter()
+        .map(|tool| {
+            // Project-local binaries win over global ones, as they are what `dolph` ends up running.
+            let local_bin = root.join("node_modules").join(".bin").join(tool);
+            let version = if local_bin.exists() {
+                bin_version(&local_bin.to_string_lossy())
+            } else {
+                bin_version(tool)
+            };
+
+            let required = engines
+                .and_then(|engines| engines.get(*tool))
+                .and_then(|range| range.as_str())
+                .map(|range| range.to_string());
+
+            let status = match (&version, &required) {
+                (Some(version), Some(range)) => {
+                    if satisfies(version, range) {
+                        Status::Ok
+                    } else {
+                        Status::Error
+                    }
+                }
+                (Some(_), None) => Status::Ok,
+                (None, _) if *tool == "node" => Status::Error,
+                (None, _) if required.is_some() => Status::Error,
+                (None, _) => Status::Warning,
+            };
+
+            ToolReport {
+                name: tool.to_string(),
+                version,
+                required,
+                status,
+            }
+        })
+        .collect()
+}
+
+fn check_project(
+    root: &Path,
+    config: Option<Result<DefaultConfig>>,
+    package_json: Option<&Value>,
+) -> Vec<CheckReport> {
+    let mut checks = Vec::new();
+
+    let config = match config {
+        None => {
+            checks.push(check(
+                "dolph_cli.yaml",
+                Status::Error,
+                "not found, run `dolph new .` to initialize a dolph project",
+            ));
+            return checks;
+        }
+        Some(Err(e)) => {
+            checks.push(check(
+                "dolph_cli.yaml",
+                Status::Error,
+                &format!("could not be read: {}", e),
+            ));
+            return checks;
+        }
+        Some(Ok(config)) => {
+            checks.push(check("dolph_cli.yaml", Status::Ok, "found"));
+            config
+        }
+    };
+
+    checks.push(match package_json {
+        Some(_) => check("package.json", Status::Ok, "found"),
+        None => check("package.json", Status::Error, "missing or not valid JSON"),
+    });
+
+    // Express projects bring their own server, only the one `dolph new` writes is expected
+    if let Some(server_file) = config.server_file() {
+        checks.push(if root.join("src").join(server_file).exists() {
+            check(
+                server_file,
+                Status::Ok,
+                &format!("found in src/{}", server_file),
+            )
+        } else {
+            check(
+                server_file,
+                Status::Error,
+                &format!("src/{} is missing", server_file),
+            )
+        });
+    }
+
+    if config.language == Language::Ts {
+        checks.extend(check_compiler_configs(root, &config));
+    }
+
+    checks.push(if root.join("node_modules").is_dir() {
+        check("node_modules", Status::Ok, "dependencies are installed")
+    } else {
+        check(
+            "node_modules",
+            Status::Error,
+            "missing, install dependencies with your package manager",
+        )
+    });
+
+    let port = configured_port(root);
+    checks.push(if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+        check("port", Status::Ok, &format!("{} is free", port))
+    } else {
+        check(
+            "port",
+            Status::Error,
+            &format!("{} is already in use", port),
+        )
+    });
+
+    checks
+}
+
+fn check_compiler_configs(root: &Path, config: &DefaultConfig) -> Vec<CheckReport> {
+    let mut checks = Vec::new();
+
+    let tsconfig = read_json(&root.join("tsconfig.json"));
+    let swcrc = read_json(&root.join(".swcrc"));
+
+    for (name, file) in [("tsconfig.json", &tsconfig), (".swcrc", &swcrc)] {
+        checks.push(match file {
+            Some(_) => check(name, Status::Ok, "found"),
+            None => check(name, Status::Error, "missing or not valid JSON"),
+        });
+    }
+
+    let (Some(tsconfig), Some(swcrc)) = (tsconfig, swcrc) else {
+        return checks;
+    };
+
+    let ts_base_url = tsconfig.pointer("/compilerOptions/baseUrl");
+    let swc_base_url = swcrc.pointer("/jsc/baseUrl");
+
+    if ts_base_url != swc_base_url {
+        checks.push(check(
+            "baseUrl",
+            Status::Error,
+            "tsconfig.json and .swcrc use different baseUrl values",
+        ));
+    }
+
+    let ts_paths = path_aliases(tsconfig.pointer("/compilerOptions/paths"));
+    let swc_paths = path_aliases(swcrc.pointer("/jsc/paths"));
+
+    let only_ts: Vec<&String> = ts_paths.iter().filter(|p| !swc_paths.contains(p)).collect();
+    let only_swc: Vec<&String> = swc_paths.iter().filter(|p| !ts_paths.contains(p)).collect();
+
+    if only_ts.is_empty() && only_swc.is_empty() {
+        checks.push(check(
+            "path aliases",
+            Status::Ok,
+            "tsconfig.json and .swcrc agree",
+        ));
+    } else {
+        let mut differences = Vec::new();
+        if !only_ts.is_empty() {
+            differences.push(format!("only in tsconfig.json: {:?}", only_ts));
+        }
+        if !only_swc.is_empty() {
+            differences.push(format!("only in .swcrc: {:?}", only_swc));
+        }
+        checks.push(check(
+            "path aliases",
+            Status::Error,
+            &differences.join("; "),
+        ));
+    }
+
+    if config.routing == Routing::Spring && !ts_paths.contains(&String::from("@/components/*")) {
+        checks.push(check(
+            "path aliases",
+            Status::Error,
+            "spring routing is configured but the `@/components/*` alias is missing",
+        ));
+    }
+
+    checks
+}
+
+fn path_aliases(paths: Option<&Value>) -> Vec<String> {
+    paths
+        .and_then(|paths| paths.as_object())
+        .map(|paths| paths.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn configured_port(root: &Path) -> u16 {
+    fs::read_to_string(root.join("dolph_config.yaml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|config| match config.get("port") {
+            Some(serde_yaml::Value::Number(port)) => port.as_u64().map(|p| p.to_string()),
+            Some(serde_yaml::Value::String(port)) => Some(port.clone()),
+            _ => None,
+        })
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_PORT)
+}
+
+fn read_json(path: &PathBuf) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Checks a version against an npm-style range such as `>=18.0.0`, `^20 || ^22` or `>=18 <21`.
+fn satisfies(version: &str, range: &str) -> bool {
+    let Some(version) = normalize_version(version) else {
+        return false;
+    };
+
+    range.split("||").any(|alternative| {
+        let comparators = comparators(alternative).join(", ");
+
+        if comparators.is_empty() || comparators == "*" {
+            return true;
+        }
+
+        VersionReq::parse(&comparators)
+            .map(|req| req.matches(&version))
+            .unwrap_or(false)
+    })
+}
+
+/// Splits a range on whitespace, keeping an operator written apart from its version (`>= 18`)
+/// together with it.
+fn comparators(range: &str) -> Vec<String> {
+    let mut comparators: Vec<String> = Vec::new();
+    for token in range.split_whitespace() {
+        match comparators.last_mut() {
+            Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(token),
+            _ => comparators.push(token.to_string()),
+        }
+    }
+    comparators
+}
+
+fn normalize_version(version: &str) -> Option<Version> {
+    let mut parts: Vec<&str> = version.split('.').take(3).collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    Version::parse(&parts.join(".")).ok()
+}
+
+fn check(name: &str, status: Status, message: &str) -> CheckReport {
+    CheckReport {
+        name: name.to_string(),
+        status,
+        message: message.to_string(),
+    }
+}
+
+fn symbol(status: Status) -> &'static str {
+    match status {
+        Status::Ok => "✔",
+        Status::Warning => "⚠",
+        Status::Error => "✖",
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    println!("Environment");
+    for tool in &report.tools {
+        let version = tool
+            .version
+            .clone()
+            .unwrap_or_else(|| String::from("not installed"));
+
+        match &tool.required {
+            Some(required) => println!(
+                "  {} {:<8} {} (engines: {})",
+                symbol(tool.status),
+                tool.name,
+                version,
+                required
+            ),
+            None => println!("  {} {:<8} {}", symbol(tool.status), tool.name, version),
+        }
+    }
+
+    println!();
+    println!("Project");
+    for check in &report.checks {
+        println!(
+            "  {} {}: {}",
+            symbol(check.status),
+            check.name,
+            check.message
+        );
+    }
+
+    println!();
+    match report.problems() {
+        0 => println!("No problems found, you're good to go. 🚀"),
+        1 => println!("Found 1 problem. 🩺"),
+        n => println!("Found {} problems. 🩺", n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::{check_project, read_json, satisfies, Status};
+    use crate::init::scaffold_project;
+    use crate::project::Project;
+    use crate::writers::tests::combinations;
+    use crate::writers::MergePolicy;
+
+    #[test]
+    fn scaffolded_projects_pass_the_project_checks() {
+        for config in combinations() {
+            let root = TempDir::new().unwrap();
+            scaffold_project(
+                &Project::new(root.path(), config.clone()),
+                "app",
+                MergePolicy::Keep,
+            )
+            .unwrap();
+            fs::create_dir(root.path().join("node_modules")).unwrap();
+
+            let package_json = read_json(&root.path().join("package.json"));
+            let failed: Vec<String> =
+                check_project(root.path(), Some(Ok(config.clone())), package_json.as_ref())
+                    .into_iter()
+                    // Whether the port is free depends on the machine
+                    .filter(|check| check.name != "port" && check.status != Status::Ok)
+                    .map(|check| format!("{}: {}", check.name, check.message))
+                    .collect();
+
+            assert!(failed.is_empty(), "{:?}: {:?}", config, failed);
+        }
+    }
+
+    #[test]
+    fn engine_ranges_allow_spaces_after_operators() {
+        assert!(satisfies("18.2.0", ">= 18"));
+        assert!(satisfies("20.1.0", ">= 18 < 21"));
+        assert!(!satisfies("16.20.0", ">= 18"));
+        assert!(!satisfies("22.0.0", ">=18 <21"));
+        assert!(satisfies("22.3.0", "^20 || >= 22"));
+    }
+}
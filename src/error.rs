@@ -0,0 +1,169 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::ConfigError;
+
+pub type Result<T> = std::result::Result<T, DolphError>;
+
+/// Process exit codes, one per error category. These are part of the CLI's public interface,
+/// scripts may rely on them, so existing values must never change.
+pub mod exit_code {
+    /// The command completed successfully.
+    pub const SUCCESS: i32 = 0;
+    /// An unexpected error, such as a failed prompt or an I/O error outside of the other categories.
+    pub const GENERAL: i32 = 1;
+    /// The command was invoked incorrectly, e.g. an invalid name or an existing project.
+    pub const USAGE: i32 = 2;
+    /// `dolph_cli.yaml` could not be found, read or validated.
+    pub const CONFIG: i32 = 3;
+    /// A generated file or directory could not be written.
+    pub const WRITE: i32 = 4;
+    /// A required runtime is missing, or the server failed to start or exited with an error.
+    pub const RUNTIME: i32 = 5;
+    /// Compiling the project failed.
+    pub const BUILD: i32 = 6;
+    /// `dolph doctor` found problems.
+    pub const DOCTOR: i32 = 7;
+    /// Installing the dependencies of a new project failed.
+    pub const INSTALL: i32 = 8;
+}
+
+#[derive(Debug)]
+pub enum DolphError {
+    Usage(String),
+    InvalidName {
+        name: String,
+        reason: String,
+        suggestion: Option<String>,
+    },
+    ProjectExists(PathBuf),
+    Config(ConfigError),
+    BaseDirectoryNotFound,
+    Write {
+        path: PathBuf,
+        source: io::Error,
+    },
+    RuntimeNotFound(String),
+    Runtime(String),
+    Build(String),
+    Install(String),
+    Doctor {
+        problems: usize,
+    },
+    Prompt(dialoguer::Error),
+    Serialization(String),
+    Io(io::Error),
+}
+
+impl DolphError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DolphError::Usage(_)
+            | DolphError::InvalidName { .. }
+            | DolphError::ProjectExists(_) => exit_code::USAGE,
+            DolphError::Config(_) | DolphError::BaseDirectoryNotFound => exit_code::CONFIG,
+            DolphError::Write { .. } => exit_code::WRITE,
+            DolphError::RuntimeNotFound(_) | DolphError::Runtime(_) => exit_code::RUNTIME,
+            DolphError::Build(_) => exit_code::BUILD,
+            DolphError::Install(_) => exit_code::INSTALL,
+            DolphError::Doctor { .. } => exit_code::DOCTOR,
+            DolphError::Prompt(_) | DolphError::Serialization(_) | DolphError::Io(_) => {
+                exit_code::GENERAL
+            }
+        }
+    }
+
+    pub fn write(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        DolphError::Write {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for DolphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DolphError::Usage(message) => write!(f, "{}", message),
+            DolphError::InvalidName {
+                name,
+                reason,
+                suggestion,
+            } => {
+                write!(f, "`{}` is not a valid name, {}", name, reason)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", try `{}` instead", suggestion),
+                    None => Ok(()),
+                }
+            }
+            DolphError::ProjectExists(path) => write!(
+                f,
+                "{} already has dolph configurations present",
+                path.display()
+            ),
+            DolphError::Config(e) => write!(f, "{}", e),
+            DolphError::BaseDirectoryNotFound => {
+                write!(f, "Could not find the src directory of the project")
+            }
+            DolphError::Write { path, source } => {
+                write!(f, "Could not write {}: {}", path.display(), source)
+            }
+            DolphError::RuntimeNotFound(bin) => write!(f, "{} is not installed", bin),
+            DolphError::Runtime(message) => write!(f, "{}", message),
+            DolphError::Build(message) => write!(f, "{}", message),
+            DolphError::Install(message) => {
+                write!(f, "Could not install the dependencies: {}", message)
+            }
+            DolphError::Doctor { problems: 1 } => write!(f, "dolph doctor found 1 problem"),
+            DolphError::Doctor { problems } => {
+                write!(f, "dolph doctor found {} problems", problems)
+            }
+            DolphError::Prompt(e) => write!(f, "Prompt failed: {}", e),
+            DolphError::Serialization(message) => write!(f, "{}", message),
+            DolphError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DolphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DolphError::Config(e) => Some(e),
+            DolphError::Write { source, .. } => Some(source),
+            DolphError::Prompt(e) => Some(e),
+            DolphError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConfigError> for DolphError {
+    fn from(e: ConfigError) -> Self {
+        DolphError::Config(e)
+    }
+}
+
+impl From<io::Error> for DolphError {
+    fn from(e: io::Error) -> Self {
+        DolphError::Io(e)
+    }
+}
+
+impl From<dialoguer::Error> for DolphError {
+    fn from(e: dialoguer::Error) -> Self {
+        DolphError::Prompt(e)
+    }
+}
+
+impl From<serde_json::Error> for DolphError {
+    fn from(e: serde_json::Error) -> Self {
+        DolphError::Serialization(e.to_string())
+    }
+}
+
+impl From<serde_yaml::Error> for DolphError {
+    fn from(e: serde_yaml::Error) -> Self {
+        DolphError::Serialization(e.to_string())
+    }
+}
@@ -1,135 +1,300 @@
-use clap::{arg, Command};
-use dialoguer::{theme::ColorfulTheme, Select};
-use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::fs;
+use dialoguer::{theme::ColorfulTheme, Confirm, Select};
 use std::path::Path;
-use std::process;
+use std::process::Command;
 
-use crate::properties::DefaultConfig;
+use crate::config::{Api, ConfigValue, Database, DefaultConfig, Language, Naming, Routing};
+use crate::error::{DolphError, Result};
+use crate::output::message;
+use crate::package_manager::PackageManager;
+use crate::project::Project;
+use crate::utils::{
+    ensure_directory_exists, is_bin_installed, validate_package_name, write_file, Staging,
+};
+use crate::wizard::is_interactive;
 use crate::writers::{
-    write_datasource_config, write_dolph_config, write_gitignore, write_graphql_server_file,
+    write_datasource_config, write_db_config, write_docker_files, write_dolph_config,
+    write_env_config, write_env_files, write_gitignore, write_graphql_server_file,
     write_package_json, write_setup_file, write_spring_server_file, write_swcrc, write_tsconfig,
+    MergePolicy,
 };
 
-pub fn init_command() -> Command<'static> {
-    Command::new("new")
-        .alias("nw")
-        .about("nw")
-        .about("Creates a new dolphjs app")
-        .arg(arg!(<PROJECT_NAME> "The name of the project to create"))
+/// The theme of every interactive prompt.
+pub(crate) fn prompt_theme() -> ColorfulTheme {
+    ColorfulTheme::default()
+}
+
+/// What `dolph new` does once the files are written.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InitOptions {
+    /// Installs with this package manager instead of the detected one.
+    pub package_manager: Option<PackageManager>,
+    pub skip_install: bool,
+    pub skip_git: bool,
 }
 
-pub fn init_dolph_cli(app_name: &str) -> Result<(), Box<dyn Error>> {
+/// Creates the project `app_name` inside `base`, or initializes `base` itself when the name is `.`.
+/// The dependencies are then installed and a git repository is created, unless `options` skips
+/// them.
+pub fn init_dolph_cli(base: &Path, app_name: &str, options: InitOptions) -> Result<()> {
     if app_name.is_empty() {
-        println!("Error: provide a name for your dolph project or indicate with a '.' to use current directory.");
-        process::exit(1);
+        return Err(DolphError::Usage(String::from(
+            "provide a name for your dolph project or indicate with a '.' to use current directory",
+        )));
+    }
+
+    // Fail before anything is created when there is nothing to install with
+    let package_manager = PackageManager::detect(options.package_manager);
+    if !options.skip_install && package_manager.is_none() {
+        return Err(DolphError::RuntimeNotFound(String::from(
+            "a package manager (yarn, npm, pnpm or bun)",
+        )));
     }
+    let package_manager = package_manager.unwrap_or(PackageManager::Yarn);
 
-    let project_name = if app_name == "." {
-        Path::new(".")
-            .canonicalize()?
-            .file_name()
-            .unwrap()
-            .to_string_lossy()
-            .into_owned()
+    let (root, project_name) = if app_name == "." {
+        let root = base.canonicalize()?;
+        let Some(project_name) = root.file_name() else {
+            return Err(DolphError::Usage(format!(
+                "{} has no name to give the project, initialize a directory below it",
+                root.display()
+            )));
+        };
+        let project_name = project_name.to_string_lossy().into_owned();
+        validate_package_name(&project_name)?;
+        (root, project_name)
     } else {
-        fs::create_dir(app_name)?;
-        std::env::set_current_dir(app_name)?;
-        app_name.to_string()
+        validate_package_name(app_name)?;
+        (base.canonicalize()?.join(app_name), app_name.to_string())
     };
 
-    let current_dir = std::env::current_dir()?;
-    let src_path = current_dir.join("src");
-    let config_path = current_dir.join("dolph_cli.yaml");
-    let test_path = current_dir.join("tests");
-    let shared_path = src_path.join("shared");
-    let component_path = src_path.join("components");
-
-    // Create necessary directories
-    fs::create_dir_all(&src_path)?;
-    fs::create_dir_all(&test_path)?;
+    let config_path = root.join("dolph_cli.yaml");
 
     if !config_path.exists() {
-        let theme = ColorfulTheme::default();
+        let theme = prompt_theme();
 
-        let api_options = ["graphql", "rest"];
-        let api = api_options[Select::with_theme(&theme)
+        let api = Api::VARIANTS[Select::with_theme(&theme)
             .with_prompt("Will you be using REST or GraphQL to build?")
             .default(1)
-            .items(&api_options)
+            .items(&Api::allowed())
             .interact()?];
 
-        let language: &str;
-        let routing: &str;
+        let language: Language;
+        let routing: Routing;
 
-        if api.to_string() == "rest" {
-            let routing_options = ["express", "spring"];
-            routing = routing_options[Select::with_theme(&theme)
+        if api == Api::Rest {
+            routing = Routing::VARIANTS[Select::with_theme(&theme)
                 .with_prompt("What dolph routing are you using?")
                 .default(0)
-                .items(&routing_options)
+                .items(&Routing::allowed())
                 .interact()?];
 
-            let language_options = ["ts", "js"];
-            language = language_options[Select::with_theme(&theme)
+            language = Language::VARIANTS[Select::with_theme(&theme)
                 .with_prompt("Select your preferred language")
                 .default(0)
-                .items(&language_options)
+                .items(&Language::allowed())
                 .interact()?];
         } else {
-            routing = "spring";
-            language = "ts";
+            routing = Routing::Spring;
+            language = Language::Ts;
         }
 
-        let database_options = ["mongo", "mysql", "postgresql", "other"];
-        let database = database_options[Select::with_theme(&theme)
+        let database = Database::VARIANTS[Select::with_theme(&theme)
             .with_prompt("What is your database of choice?")
             .default(0)
-            .items(&database_options)
+            .items(&Database::allowed())
+            .interact()?];
+
+        let naming = Naming::VARIANTS[Select::with_theme(&theme)
+            .with_prompt("Should routes and tables use plural or singular names?")
+            .default(0)
+            .items(&Naming::allowed())
             .interact()?];
 
-        let mut config = DefaultConfig {
-            language: language.to_string(),
-            api: api.to_string(),
-            database: database.to_string(),
-            routing: routing.to_string(),
+        let config = DefaultConfig::new(api, routing, language, database).with_naming(naming);
+
+        // Everything is written next to the project first and only moved into place once it is
+        // complete, an existing directory keeps the files dolph did not write
+        let staging = if root.exists() {
+            Staging::new(&root, Some(&root))?
+        } else {
+            Staging::new(base, None)?
         };
+        let staged = staging.path().to_path_buf();
+        ensure_directory_exists(&staged.join("tests"))?;
+        let project = Project::new(&staged, config);
+        scaffold_project(
+            &project,
+            &project_name,
+            MergePolicy::Confirm(&confirm_merge),
+        )?;
+        staging.commit(&root)?;
+        message("dolph configurations have been initialized successfully. ✨");
 
-        let yaml_content = format!(
-            "# this is an auto-generated file, please do not edit manually\n{}",
-            serde_yaml::to_string(&config)?
-        );
-        fs::write(&config_path, yaml_content)?;
-
-        // Create additional directories for spring routing
-        if config.routing == "spring" {
-            fs::create_dir_all(&component_path)?;
-            fs::create_dir_all(&shared_path)?;
-
-            if config.api == "graphql" {
-                write_datasource_config(&mut config.database)?;
-                write_setup_file()?;
-                write_graphql_server_file()?;
-            } else {
-                write_spring_server_file(&config.database, &String::from(""))?;
-            }
+        // The project is complete either way, a failed install is reported once git is set up
+        let installed = if options.skip_install {
+            Ok(())
+        } else {
+            message(format!(
+                "Installing dependencies with {}...",
+                package_manager
+            ));
+            package_manager.install(&root)
+        };
+        if !options.skip_git {
+            init_git(&root);
         }
+        project.changes().emit("new", &staged);
+        installed?;
 
-        if config.language == "ts" {
-            write_swcrc(config.routing == "spring")?;
-            write_tsconfig(config.routing == "spring")?;
+        let start = package_manager.run_script("dev:start");
+        if options.skip_install {
+            message(format!(
+                "run `{} install` and `{}` to build the project and start development. 🚀",
+                package_manager, start
+            ));
+        } else {
+            message(format!(
+                "run `{}` to build the project and start development. 🚀",
+                start
+            ));
         }
+    } else {
+        return Err(DolphError::ProjectExists(root));
+    }
+
+    Ok(())
+}
+
+/// Creates a git repository in `root` with an initial commit. Problems are reported rather than
+/// returned, the project itself is complete without a repository.
+fn init_git(root: &Path) {
+    if !is_bin_installed("git") {
+        message("git is not installed, skipping the repository");
+        return;
+    }
 
-        write_dolph_config()?;
-        write_package_json(&project_name, &config.language, &config.api)?;
-        write_gitignore()?;
+    let git = |args: &[&str]| Command::new("git").args(args).current_dir(root).output();
 
-        println!("dolph configurations have been initialized successfully. ✨");
-        println!("run `yarn dev:start` to build the project and start development. 🚀");
-    } else {
-        println!("I see you already have your dolph configurations present, so I'll abort gracefully... 😉");
+    if git(&["rev-parse", "--is-inside-work-tree"]).is_ok_and(|output| output.status.success()) {
+        message("The project is inside a git repository already, skipping git init");
+        return;
+    }
+
+    for args in [
+        &["init", "--quiet"][..],
+        &["add", "--all"],
+        &[
+            "commit",
+            "--quiet",
+            "--message",
+            "Initial commit from dolph new",
+        ],
+    ] {
+        match git(args) {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                message(format!(
+                    "`git {}` failed: {}",
+                    args[0],
+                    stderr
+                        .lines()
+                        .find(|line| !line.trim().is_empty())
+                        .unwrap_or_default()
+                ));
+                return;
+            }
+            Err(e) => {
+                message(format!("`git {}` failed: {}", args[0], e));
+                return;
+            }
+        }
+    }
+
+    message("Initialized a git repository with an initial commit");
+}
+
+/// Asks whether to replace customized values of an existing config or file, defaulting to no. Without a
+/// terminal nothing is replaced.
+pub(crate) fn confirm_merge(prompt: &str) -> Result<bool> {
+    if !is_interactive() {
+        return Ok(false);
     }
+    Ok(Confirm::with_theme(&prompt_theme())
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?)
+}
+
+/// Writes the config and starter files of a new project, without prompting. Values of existing
+/// configs that differ from the generated ones are replaced as `merge` decides.
+pub fn scaffold_project(project: &Project, project_name: &str, merge: MergePolicy) -> Result<()> {
+    let config = project.config();
+    let src_path = project.root().join("src");
+    let shared_path = src_path.join("shared");
+    let component_path = src_path.join("components");
+
+    ensure_directory_exists(&src_path)?;
+
+    write_file(
+        project.changes(),
+        &project.root().join("dolph_cli.yaml"),
+        config.to_yaml()?,
+    )?;
+
+    // Credentials live in .env, the configs below read them through env.config.ts
+    write_env_files(project, project_name)?;
+    write_env_config(project)?;
+
+    // Create additional directories for spring routing
+    if config.routing == Routing::Spring {
+        ensure_directory_exists(&component_path)?;
+        ensure_directory_exists(&shared_path)?;
+
+        if config.api == Api::Graphql {
+            write_datasource_config(project)?;
+            write_setup_file(project)?;
+            write_graphql_server_file(project)?;
+        } else {
+            if config.database == Database::MySQL {
+                // The server initializes the sequelize instance defined there
+                write_db_config(project)?;
+            }
+            write_spring_server_file(project, None)?;
+        }
+    }
+
+    if config.language == Language::Ts {
+        write_swcrc(project, merge)?;
+        write_tsconfig(project, merge)?;
+    }
+
+    write_dolph_config(project)?;
+    write_package_json(project, project_name, merge)?;
+    write_gitignore(project)?;
+    write_docker_files(project, project_name, merge)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{init_dolph_cli, InitOptions};
+    use crate::error::DolphError;
+
+    #[test]
+    fn the_filesystem_root_cannot_be_initialized() {
+        let options = InitOptions {
+            skip_install: true,
+            skip_git: true,
+            ..InitOptions::default()
+        };
+
+        assert!(matches!(
+            init_dolph_cli(Path::new("/"), ".", options),
+            Err(DolphError::Usage(_))
+        ));
+    }
+}
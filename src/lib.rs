@@ -0,0 +1,28 @@
+//! The library behind the `dolph` CLI.
+//!
+//! Everything the CLI does is available here without shelling out: open a [`Project`], scaffold
+//! files into it with a [`Generator`] or the [`writers`], and build or run it with the [`daemon`].
+//! Errors are returned as [`DolphError`], whose exit codes are part of the CLI's interface.
+
+pub mod add;
+pub mod config;
+pub mod daemon;
+pub mod doctor;
+pub mod error;
+pub mod init;
+pub mod output;
+pub mod package_manager;
+mod project;
+mod properties;
+mod utils;
+pub mod wizard;
+pub mod writers;
+
+pub use config::{parse_config, DefaultConfig};
+pub use error::{DolphError, Result};
+pub use init::scaffold_project;
+pub use project::Project;
+pub use properties::{Generator, Schematic};
+pub use utils::{
+    read_config, resolve_project_root, Field, FieldType, ResourceName, CONFIG_FILE_NAME,
+};
@@ -1,82 +1,144 @@
-use std::error::Error;
+use std::path::PathBuf;
+use std::process;
 
-use clap::Command;
-use daemon::{
-    build_command, build_ts_app, init_start_command, init_watch_command, start_command,
-    watch_command,
+use clap::{Arg, ArgMatches, Command};
+use cli::{
+    add_command, build_command, doctor_command, init_architecture, init_command,
+    run_init_architecture, start_command, watch_command,
 };
-use init::{init_command, init_dolph_cli};
-use properties::{init_architecture, run_init_architecture};
-use utils::read_config;
+use dolph::add::{run_add, Integration};
+use dolph::config::DefaultConfig;
+use dolph::daemon::{build_ts_app, run_start, run_watch};
+use dolph::doctor::run_doctor;
+use dolph::error::{exit_code, Result};
+use dolph::init::{init_dolph_cli, InitOptions};
+use dolph::output::{self, is_json, set_format, Event, OutputFormat};
+use dolph::package_manager::PackageManager;
+use dolph::{Field, Generator, Project};
 
-mod daemon;
-mod init;
-mod properties;
-mod utils;
-mod writers;
+mod cli;
 
-#[derive(Debug)]
-pub enum ServerFileError {
-    BaseDirectoryNotFound,
-    InvalidDatabase(String),
-    IoError(std::io::Error),
-}
-
-impl std::fmt::Display for ServerFileError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ServerFileError::BaseDirectoryNotFound => write!(f, "Base directory not found"),
-            ServerFileError::InvalidDatabase(db) => write!(f, "Invalid database type: {}", db),
-            ServerFileError::IoError(e) => write!(f, "IO error: {}", e),
+#[tokio::main]
+async fn main() {
+    // Errors are only ever printed here, everything else returns them
+    let code = match run().await {
+        Ok(()) => exit_code::SUCCESS,
+        Err(e) => {
+            if is_json() {
+                output::event(&Event::Error {
+                    code: e.exit_code(),
+                    message: e.to_string(),
+                });
+            } else {
+                eprintln!("[Dolph Error]: {}", e);
+            }
+            e.exit_code()
         }
-    }
-}
+    };
 
-impl Error for ServerFileError {}
+    process::exit(code);
+}
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn run() -> Result<()> {
     let matches = Command::new("dolph")
+        .arg(
+            Arg::new("cwd")
+                .long("cwd")
+                .value_name("DIR")
+                .help("Runs as if dolph was started in <DIR>")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FORMAT")
+                .help("Output format, `json` prints newline-delimited JSON events")
+                .possible_values(["text", "json"])
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Shorthand for --output json")
+                .global(true),
+        )
         .subcommand(init_command())
         .subcommand(init_architecture())
         .subcommand(watch_command())
         .subcommand(start_command())
         .subcommand(build_command())
+        .subcommand(doctor_command())
+        .subcommand(add_command())
         .get_matches();
 
+    // Global args are only propagated down, so they are read from the subcommand
+    let global_matches = matches
+        .subcommand()
+        .map(|(_, matches)| matches)
+        .unwrap_or(&matches);
+    let output_format = if global_matches.is_present("json") {
+        OutputFormat::Json
+    } else {
+        global_matches
+            .value_of("output")
+            .and_then(OutputFormat::from_name)
+            .unwrap_or(OutputFormat::Text)
+    };
+    set_format(output_format);
+
     if let Some(matches) = matches.subcommand_matches("new") {
         let project_name = matches.value_of("PROJECT_NAME").unwrap();
-        init_dolph_cli(project_name)?;
+        let base = match matches.value_of("cwd") {
+            Some(cwd) => PathBuf::from(cwd),
+            None => std::env::current_dir()?,
+        };
+        let options = InitOptions {
+            package_manager: matches
+                .value_of("package-manager")
+                .and_then(PackageManager::parse),
+            skip_install: matches.is_present("skip-install"),
+            skip_git: matches.is_present("skip-git"),
+        };
+        init_dolph_cli(&base, project_name, options)?;
     } else if let Some(matchess) = matches.subcommand_matches("generate") {
-        match read_config() {
-            Ok(config) => {
-                let generator = properties::Generator::new(config);
-                run_init_architecture(generator, matchess).await?;
-            }
-            Err(e) => eprintln!("Failed to read config file: {}", e),
-        }
+        let project = Project::open(matchess.value_of("cwd"))?;
+        let fields = Field::parse_list(matchess.value_of("fields").unwrap_or_default())?;
+        let generator = Generator::new(project)
+            .with_crud(matchess.is_present("crud"))
+            .with_fields(fields)
+            .with_register(matchess.is_present("register"))
+            .with_apply_to(matchess.value_of("apply-to").map(String::from));
+        run_init_architecture(&generator, matchess).await?;
+        let project = generator.project();
+        project.changes().emit("generate", project.root());
+    } else if let Some(matchess) = matches.subcommand_matches("add") {
+        let project = Project::open(matchess.value_of("cwd"))?;
+        // clap only accepts the possible values
+        let integration = Integration::parse(matchess.value_of("INTEGRATION").unwrap()).unwrap();
+        run_add(&project, integration, matchess.value_of("NAME")).await?;
+        project.changes().emit("add", project.root());
     } else if let Some(matchess) = matches.subcommand_matches("watch") {
-        match read_config() {
-            Ok(config) => {
-                init_watch_command(&config.language, matchess);
-            }
-            Err(e) => eprintln!("Failed to read config file: {}", e),
-        }
+        let config = enter_project(matchess)?;
+        run_watch(config.language, matchess.is_present("bun"))?;
     } else if let Some(matchess) = matches.subcommand_matches("start") {
-        match read_config() {
-            Ok(config) => {
-                init_start_command(&config.language, matchess);
-            }
-            Err(e) => eprintln!("Failed to read config file: {}", e),
-        }
+        let config = enter_project(matchess)?;
+        run_start(config.language, matchess.is_present("bun"))?;
     } else if let Some(matchess) = matches.subcommand_matches("build") {
-        match read_config() {
-            Ok(config) => {
-                let _ = build_ts_app(&config.language).await;
-            }
-            Err(e) => eprintln!("Failed to read config file: {}", e),
-        }
+        let config = enter_project(matchess)?;
+        build_ts_app(config.language).await?;
+    } else if let Some(matchess) = matches.subcommand_matches("doctor") {
+        run_doctor(matchess.value_of("cwd"))?;
     }
 
     Ok(())
 }
+
+/// Opens the project and makes its root the working directory, the daemon runs swc and node
+/// against it.
+fn enter_project(matches: &ArgMatches) -> Result<DefaultConfig> {
+    let project = Project::open(matches.value_of("cwd"))?;
+    std::env::set_current_dir(project.root())?;
+    Ok(project.config().clone())
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileAction {
+    Created,
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub action: FileAction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: &'static str,
+    pub message: String,
+}
+
+/// Events printed in JSON mode, one JSON object per line, tagged by `event`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// Files written by `new` and `generate`, relative to the project root.
+    Files {
+        command: &'a str,
+        files: Vec<FileChange>,
+    },
+    /// The result of `build`.
+    Compile {
+        success: bool,
+        duration_ms: u128,
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// The server was started, by `watch` or `start`.
+    Start { program: &'a str, timestamp: u128 },
+    /// A watched file changed.
+    Change { path: &'a Path, timestamp: u128 },
+    /// The server was restarted after a change.
+    Restart { timestamp: u128 },
+    /// The server exited on its own with a non-zero status.
+    Crash {
+        exit_code: Option<i32>,
+        timestamp: u128,
+    },
+    /// The server exited on its own with a zero status.
+    Exit { timestamp: u128 },
+    /// The daemon was stopped.
+    Stop { timestamp: u128 },
+    /// The command failed, printed once right before exiting.
+    Error { code: i32, message: String },
+}
+
+pub fn set_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+pub fn is_json() -> bool {
+    FORMAT.get() == Some(&OutputFormat::Json)
+}
+
+/// Prints human-readable progress, which is suppressed in JSON mode.
+pub fn message(message: impl Display) {
+    if !is_json() {
+        println!("{}", message);
+    }
+}
+
+/// Prints an event as a single line of JSON, only in JSON mode.
+pub fn event(event: &Event) {
+    if is_json() {
+        if let Ok(line) = serde_json::to_string(event) {
+            println!("{}", line);
+        }
+    }
+}
+
+pub fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+/// Records the files a command writes, for the `files` event printed in JSON mode.
+///
+/// Clones share the same list, so a [`Project`](crate::Project) and everything derived from it
+/// report into one place.
+#[derive(Debug, Clone, Default)]
+pub struct FileChanges(Arc<Mutex<Vec<FileChange>>>);
+
+impl FileChanges {
+    pub fn record(&self, path: &Path, action: FileAction) {
+        if let Ok(mut changes) = self.0.lock() {
+            changes.push(FileChange {
+                path: path.to_path_buf(),
+                action,
+            });
+        }
+    }
+
+    /// Takes the files recorded so far.
+    pub fn take(&self) -> Vec<FileChange> {
+        self.0
+            .lock()
+            .map(|mut changes| std::mem::take(&mut *changes))
+            .unwrap_or_default()
+    }
+
+    /// Emits the files recorded so far as a `files` event, with paths relative to `root`.
+    pub fn emit(&self, command: &str, root: &Path) {
+        let files = self
+            .take()
+            .into_iter()
+            .map(|change| FileChange {
+                path: change
+                    .path
+                    .strip_prefix(root)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(change.path),
+                action: change.action,
+            })
+            .collect();
+
+        event(&Event::Files { command, files });
+    }
+}
@@ -0,0 +1,112 @@
+//! The package manager `dolph new` installs dependencies with.
+
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{DolphError, Result};
+use crate::output::is_json;
+use crate::utils::is_bin_installed;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Yarn,
+    Npm,
+    Pnpm,
+    Bun,
+}
+
+impl PackageManager {
+    /// In the order they are looked for when none is given.
+    pub const VARIANTS: &'static [Self] = &[
+        PackageManager::Yarn,
+        PackageManager::Npm,
+        PackageManager::Pnpm,
+        PackageManager::Bun,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageManager::Yarn => "yarn",
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|variant| variant.as_str() == value)
+            .copied()
+    }
+
+    /// The package manager that runs dolph, read from the `npm_config_user_agent` every package
+    /// manager sets for the binaries it runs, e.g. `yarn/1.22.19 npm/? node/v20.11.0`.
+    pub fn from_user_agent(user_agent: &str) -> Option<Self> {
+        Self::parse(user_agent.split('/').next()?.trim())
+    }
+
+    /// `preferred` when given, otherwise the package manager dolph was run with, otherwise the
+    /// first installed one.
+    pub fn detect(preferred: Option<Self>) -> Option<Self> {
+        preferred
+            .or_else(|| {
+                std::env::var("npm_config_user_agent")
+                    .ok()
+                    .and_then(|user_agent| Self::from_user_agent(&user_agent))
+            })
+            .or_else(|| {
+                Self::VARIANTS
+                    .iter()
+                    .find(|variant| is_bin_installed(variant.as_str()))
+                    .copied()
+            })
+    }
+
+    /// The command that runs the package.json `script`.
+    pub fn run_script(&self, script: &str) -> String {
+        match self {
+            PackageManager::Yarn | PackageManager::Pnpm => format!("{} {}", self, script),
+            PackageManager::Npm | PackageManager::Bun => format!("{} run {}", self, script),
+        }
+    }
+
+    /// Installs the dependencies of the project in `root`.
+    pub fn install(&self, root: &Path) -> Result<()> {
+        // Keep stdout for the JSON events, the installer's progress goes to stderr
+        let stdout = if is_json() {
+            Stdio::from(std::io::stderr())
+        } else {
+            Stdio::inherit()
+        };
+
+        let status = Command::new(self.as_str())
+            .arg("install")
+            .current_dir(root)
+            .stdout(stdout)
+            .status()
+            .map_err(|e| match e.kind() {
+                ErrorKind::NotFound => DolphError::RuntimeNotFound(self.to_string()),
+                _ => DolphError::Install(format!("could not run {}: {}", self, e)),
+            })?;
+
+        if !status.success() {
+            return Err(DolphError::Install(format!(
+                "`{} install` failed ({}), run it again in {}",
+                self,
+                status,
+                root.display()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
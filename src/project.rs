@@ -0,0 +1,72 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::DefaultConfig;
+use crate::error::{DolphError, Result};
+use crate::output::FileChanges;
+use crate::utils::{read_config, resolve_project_root};
+
+/// The project the writers and the generator operate on.
+///
+/// Every path is derived from the project root rather than the working directory, so several
+/// projects can be generated into from a single process. The files written into the project are
+/// recorded in [`Project::changes`], which clones of the project share.
+#[derive(Debug, Clone)]
+pub struct Project {
+    root: PathBuf,
+    src: PathBuf,
+    config: DefaultConfig,
+    changes: FileChanges,
+}
+
+impl Project {
+    pub fn new(root: impl Into<PathBuf>, config: DefaultConfig) -> Self {
+        let root = root.into();
+        Self {
+            src: root.join("src"),
+            root,
+            config,
+            changes: FileChanges::default(),
+        }
+    }
+
+    /// Opens the project found from `--cwd`, `DOLPH_PROJECT_ROOT` or the working directory, see
+    /// [`resolve_project_root`].
+    pub fn open(cwd: Option<&str>) -> Result<Self> {
+        let root = resolve_project_root(cwd)?;
+        let changes = FileChanges::default();
+        let config = read_config(&root, &changes)?;
+        Ok(Self {
+            changes,
+            ..Self::new(root, config)
+        })
+    }
+
+    /// The same project with an updated config, recording into the same [`FileChanges`].
+    pub fn with_config(&self, config: DefaultConfig) -> Self {
+        Self {
+            config,
+            ..self.clone()
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The `src` directory, which has to exist before anything is generated into it.
+    pub fn src_dir(&self) -> Result<&Path> {
+        if self.src.is_dir() {
+            Ok(&self.src)
+        } else {
+            Err(DolphError::BaseDirectoryNotFound)
+        }
+    }
+
+    pub fn config(&self) -> &DefaultConfig {
+        &self.config
+    }
+
+    pub fn changes(&self) -> &FileChanges {
+        &self.changes
+    }
+}
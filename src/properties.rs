@@ -1,146 +1,536 @@
-use std::error::Error;
+use crate::config::{Api, Database, DefaultConfig, Routing};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DolphError, Result};
+use crate::output::message;
+use crate::project::Project;
+use crate::utils::{Field, ResourceName};
+use crate::writers::{
+    apply_to_controller, middleware_import, register_entity, register_resolver,
+    register_spring_component, register_spring_socket, shared_import, write_db_config,
+    write_entity, write_graphql_service, write_input, write_middleware, write_resolver,
+    write_shared_file, write_shield, write_socket_component, write_socket_service,
+    write_spring_component, write_spring_controller, write_spring_dto, write_spring_model,
+    write_spring_server_file, write_spring_service, SharedKind,
+};
 
-use clap::{arg, ArgMatches, Command};
+/// The kinds of files `dolph generate` can scaffold, one per flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schematic {
+    All,
+    Controller,
+    Service,
+    Route,
+    Model,
+    Entity,
+    Dto,
+    Input,
+    Resolver,
+    Socket,
+    Component,
+    Middleware,
+    Shield,
+    Decorator,
+    Helper,
+    Constant,
+    Interface,
+    Validation,
+}
 
-use serde::{Deserialize, Serialize};
+impl Schematic {
+    /// In the order `dolph generate` runs them when several flags are given.
+    pub const VARIANTS: &'static [Self] = &[
+        Schematic::Controller,
+        Schematic::Service,
+        Schematic::Route,
+        Schematic::Model,
+        Schematic::Entity,
+        Schematic::Dto,
+        Schematic::Input,
+        Schematic::Resolver,
+        Schematic::Socket,
+        Schematic::Component,
+        Schematic::Middleware,
+        Schematic::Shield,
+        Schematic::Decorator,
+        Schematic::Helper,
+        Schematic::Constant,
+        Schematic::Interface,
+        Schematic::Validation,
+        Schematic::All,
+    ];
+
+    /// The name of the `dolph generate` flag.
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Schematic::All => "all",
+            Schematic::Controller => "controller",
+            Schematic::Service => "service",
+            Schematic::Route => "route",
+            Schematic::Model => "model",
+            Schematic::Entity => "entity",
+            Schematic::Dto => "dto",
+            Schematic::Input => "input",
+            Schematic::Resolver => "resolver",
+            Schematic::Socket => "socket",
+            Schematic::Component => "component",
+            Schematic::Middleware => "middleware",
+            Schematic::Shield => "shield",
+            Schematic::Decorator => "decorator",
+            Schematic::Helper => "helper",
+            Schematic::Constant => "constant",
+            Schematic::Interface => "interface",
+            Schematic::Validation => "validation",
+        }
+    }
 
-use crate::writers::{
-    write_db_config, write_entity, write_graphql_service, write_input, write_resolver, write_socket_service, write_spring_component, write_spring_controller, write_spring_dto, write_spring_model, write_spring_server_file, write_spring_service
-};
+    /// The schematics that make sense for the api and routing of `config`.
+    pub fn available(config: &DefaultConfig) -> Vec<Self> {
+        let mut schematics = vec![Schematic::All, Schematic::Service];
+        if config.serves(Api::Graphql) {
+            schematics.extend([Schematic::Entity, Schematic::Input, Schematic::Resolver]);
+        }
+        match (config.api, config.routing) {
+            (Api::Graphql, _) => {}
+            (Api::Rest, Routing::Express) => schematics.extend([
+                Schematic::Controller,
+                Schematic::Model,
+                Schematic::Dto,
+                Schematic::Middleware,
+            ]),
+            (Api::Rest, Routing::Spring) => schematics.extend([
+                Schematic::Controller,
+                Schematic::Model,
+                Schematic::Dto,
+                Schematic::Component,
+                Schematic::Socket,
+                Schematic::Middleware,
+                Schematic::Shield,
+            ]),
+        }
+        schematics.extend([
+            Schematic::Decorator,
+            Schematic::Helper,
+            Schematic::Constant,
+            Schematic::Interface,
+            Schematic::Validation,
+        ]);
+        schematics
+    }
 
-pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+    /// Whether the schematic declares the fields given with [`Generator::with_fields`].
+    pub fn has_fields(&self) -> bool {
+        matches!(
+            self,
+            Schematic::All
+                | Schematic::Model
+                | Schematic::Entity
+                | Schematic::Dto
+                | Schematic::Input
+                | Schematic::Interface
+        )
+    }
 
-#[derive(Debug, Clone)]
-pub struct Generator {
-    config: DefaultConfig,
+    /// Whether the file goes into the shared code rather than a component, so its name cannot
+    /// clash with one.
+    pub fn is_shared(&self) -> bool {
+        matches!(
+            self,
+            Schematic::Middleware
+                | Schematic::Shield
+                | Schematic::Decorator
+                | Schematic::Helper
+                | Schematic::Constant
+                | Schematic::Interface
+                | Schematic::Validation
+        )
+    }
+
+    /// Whether the result can be applied to a controller, see [`Generator::with_apply_to`].
+    pub fn can_apply(&self) -> bool {
+        matches!(self, Schematic::Middleware | Schematic::Shield)
+    }
+
+    /// Whether the result can be registered in its component and the server, see
+    /// [`Generator::with_register`].
+    pub fn can_register(&self, config: &DefaultConfig) -> bool {
+        config.api == Api::Rest
+            && config.routing == Routing::Spring
+            && matches!(
+                self,
+                Schematic::All | Schematic::Controller | Schematic::Service | Schematic::Component
+            )
+    }
+}
+
+impl fmt::Display for Schematic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.flag())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct DefaultConfig {
-    pub language: String,
-    pub database: String,
-    pub routing: String,
-    pub api: String,
+#[derive(Debug, Clone)]
+pub struct Generator {
+    project: Project,
+    crud: bool,
+    fields: Vec<Field>,
+    register: bool,
+    apply_to: Option<String>,
 }
 
-impl DefaultConfig {
-    pub fn new(config: DefaultConfig) -> Self {
+impl Generator {
+    pub fn new(project: Project) -> Self {
         Self {
-            language: config.language.to_string(),
-            database: config.database.to_string(),
-            routing: config.routing.to_string(),
-            api: config.api.to_string(),
+            project,
+            crud: false,
+            fields: Vec::new(),
+            register: false,
+            apply_to: None,
         }
     }
-}
 
-impl Generator {
-    pub fn new(config: DefaultConfig) -> Self {
-        Self { config }
+    pub fn project(&self) -> &Project {
+        &self.project
+    }
+
+    /// Generates full create/read/update/delete scaffolding instead of a bare skeleton.
+    pub fn with_crud(mut self, crud: bool) -> Self {
+        self.crud = crud;
+        self
+    }
+
+    /// Declares `fields` on the generated models, entities, dtos and inputs.
+    pub fn with_fields(mut self, fields: Vec<Field>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Registers the generated controller and service in their component, and the component in
+    /// the server file. Only applies to spring routing.
+    pub fn with_register(mut self, register: bool) -> Self {
+        self.register = register;
+        self
+    }
+
+    /// Applies generated middlewares and shields to a controller, given as `<controller>` for the
+    /// whole controller or `<controller>:<handler>` for a single route.
+    pub fn with_apply_to(mut self, target: Option<String>) -> Self {
+        self.apply_to = target;
+        self
+    }
+
+    /// The controller and handler of [`Generator::with_apply_to`], once the controller is known to
+    /// exist.
+    pub fn apply_target(&self) -> Result<Option<(ResourceName, Option<&str>)>> {
+        let Some(target) = &self.apply_to else {
+            return Ok(None);
+        };
+
+        let (controller, method) = match target.split_once(':') {
+            Some((controller, method)) => (controller, Some(method)),
+            None => (target.as_str(), None),
+        };
+        if let Some(method) = method {
+            ResourceName::parse(method)?;
+        }
+
+        let controller = ResourceName::parse(controller)?;
+        let controller_file = controller
+            .dir(&self.project.src_dir()?.join("components"))
+            .join(format!("{}.controller.ts", controller.kebab()));
+        if !controller_file.is_file() {
+            return Err(DolphError::Usage(format!(
+                "there is no `{}` controller to apply to, generate it with `--controller {}`",
+                controller.path(),
+                controller.path()
+            )));
+        }
+
+        Ok(Some((controller, method)))
+    }
+
+    pub async fn generate(&self, schematic: Schematic, name: &str) -> Result<()> {
+        match schematic {
+            Schematic::All => self.generate_all(name).await?,
+            Schematic::Controller => self.generate_controller(name).await?,
+            Schematic::Service => self.generate_service(name).await?,
+            Schematic::Route => {
+                if self.project.config().routing == Routing::Spring {
+                    message("Cannot create routes file for spring routing");
+                } else {
+                    self.generate_route(name).await?;
+                }
+            }
+            Schematic::Model => self.generate_model(name).await?,
+            Schematic::Entity => self.generate_entity(name).await?,
+            Schematic::Dto => self.generate_dto(name).await?,
+            Schematic::Input => self.generate_input(name).await?,
+            Schematic::Resolver => self.generate_resolver(name).await?,
+            Schematic::Socket => self.generate_socket(name).await?,
+            Schematic::Component => self.generate_component(name).await?,
+            Schematic::Middleware => self.generate_middleware(name).await?,
+            Schematic::Shield => self.generate_shield(name).await?,
+            Schematic::Decorator => self.generate_shared(SharedKind::Decorator, name)?,
+            Schematic::Helper => self.generate_shared(SharedKind::Helper, name)?,
+            Schematic::Constant => self.generate_shared(SharedKind::Constant, name)?,
+            Schematic::Interface => self.generate_shared(SharedKind::Interface, name)?,
+            Schematic::Validation => self.generate_shared(SharedKind::Validation, name)?,
+        }
+
+        if self.register && schematic.can_register(self.project.config()) {
+            self.register_component(name).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses and validates `name`, see [`ResourceName::parse`], and makes sure it does not clash
+    /// with a component of the same name in another namespace, as both would export the same
+    /// classes.
+    pub fn resource(&self, name: &str) -> Result<ResourceName> {
+        let resource = ResourceName::parse(name)?;
+
+        let Ok(src) = self.project.src_dir() else {
+            return Ok(resource);
+        };
+        let components = src.join("components");
+        let target = resource.dir(&components);
+
+        if let Some(existing) = find_component(&components, &resource.kebab())
+            .into_iter()
+            .find(|dir| *dir != target)
+        {
+            let existing = existing.strip_prefix(src).unwrap_or(&existing);
+            return Err(DolphError::InvalidName {
+                name: name.to_string(),
+                reason: format!(
+                    "a component with the same class names already exists in {}",
+                    existing.display()
+                ),
+                suggestion: resource.qualified(),
+            });
+        }
+
+        Ok(resource)
+    }
+
+    /// Whether to generate CRUD scaffolding, rejecting `--crud` when the configured database has
+    /// no generated model to back it.
+    fn crud(&self) -> Result<bool> {
+        let config = self.project.config();
+        if self.crud && !config.supports_crud() {
+            return Err(DolphError::Usage(format!(
+                "--crud needs a mongo or mysql database in rest projects, {} has no generated model",
+                config.database
+            )));
+        }
+        Ok(self.crud)
     }
 
     pub async fn generate_controller(&self, name: &str) -> Result<()> {
-        write_spring_controller(name)?;
-        println!("Generated controller: {}", name);
+        write_spring_controller(&self.project, &self.resource(name)?, self.crud()?)?;
+        message(format!("Generated controller: {}", name));
         Ok(())
     }
 
     pub async fn generate_service(&self, name: &str) -> Result<()> {
-        if self.config.api == "graphql" {
-            write_graphql_service(name)?;
-        } else {
-            write_spring_service(&self.config.database, name)?;
+        // Rest projects serving graphql as well get a service for each
+        let resource = self.resource(name)?;
+        if self.project.config().api == Api::Rest {
+            write_spring_service(&self.project, &resource, self.crud()?)?;
+        }
+        if self.project.config().serves(Api::Graphql) {
+            write_graphql_service(&self.project, &resource, self.crud)?;
         }
 
-        println!("Generated service: {}", name);
+        message(format!("Generated service: {}", name));
         Ok(())
     }
 
     pub async fn generate_model(&self, name: &str) -> Result<()> {
-        if self.config.database == "mysql" {
+        if self.project.config().database == Database::MySQL {
             self.generate_db_config(name).await?;
         }
-        write_spring_model(&self.config.database, name)?;
-        println!("Generated model: {}", name);
+        write_spring_model(&self.project, &self.resource(name)?, &self.fields)?;
+        message(format!("Generated model: {}", name));
         Ok(())
     }
 
     pub async fn generate_route(&self, name: &str) -> Result<()> {
-        println!("Generated route: {}", name);
+        message(format!("Generated route: {}", name));
         Ok(())
     }
 
     pub async fn generate_component(&self, name: &str) -> Result<()> {
-        write_spring_component(name)?;
-        println!("Generated component: {} component", name);
+        write_spring_component(&self.project, &self.resource(name)?, self.crud()?)?;
+        message(format!("Generated component: {} component", name));
         Ok(())
     }
 
     pub async fn generate_socket(&self, name: &str) -> Result<()> {
-        write_socket_service(name)?;
-        println!("Generated socket: {}", name);
+        let resource = self.resource(name)?;
+        write_socket_service(&self.project, &resource)?;
+        write_socket_component(&self.project, &resource)?;
+        if self.project.config().routing == Routing::Spring {
+            register_spring_socket(&self.project, &resource)?;
+        }
+        message(format!("Generated socket: {}", name));
+        Ok(())
+    }
+
+    pub async fn generate_middleware(&self, name: &str) -> Result<()> {
+        let resource = ResourceName::parse(name)?;
+        let target = self.apply_target()?;
+        write_middleware(&self.project, &resource)?;
+        message(format!("Generated middleware: {}", name));
+        self.apply(
+            target,
+            &resource,
+            "middleware",
+            format!("{}Middleware", resource.camel()),
+        )
+    }
+
+    pub async fn generate_shield(&self, name: &str) -> Result<()> {
+        let resource = ResourceName::parse(name)?;
+        let target = self.apply_target()?;
+        write_shield(&self.project, &resource)?;
+        message(format!("Generated shield: {}", name));
+        self.apply(
+            target,
+            &resource,
+            "shield",
+            format!("{}Shield", resource.camel()),
+        )
+    }
+
+    fn apply(
+        &self,
+        target: Option<(ResourceName, Option<&str>)>,
+        resource: &ResourceName,
+        kind: &str,
+        export: String,
+    ) -> Result<()> {
+        let Some((controller, method)) = target else {
+            return Ok(());
+        };
+
+        apply_to_controller(
+            &self.project,
+            &controller,
+            method,
+            &export,
+            &middleware_import(&self.project, kind, resource),
+        )?;
+        message(format!(
+            "Applied {} to {}",
+            export,
+            self.apply_to.as_deref().unwrap_or_default()
+        ));
+        Ok(())
+    }
+
+    /// Writes a decorator, helper, constant, interface or validation into the shared code and its
+    /// barrel.
+    pub fn generate_shared(&self, kind: SharedKind, name: &str) -> Result<()> {
+        let resource = ResourceName::parse(name)?;
+        write_shared_file(&self.project, kind, &resource, &self.fields)?;
+        message(format!(
+            "Generated {}: {}, import {} from \"{}\"",
+            kind.suffix(),
+            name,
+            kind.export_name(&resource),
+            shared_import(kind)
+        ));
         Ok(())
     }
 
     pub async fn generate_resolver(&self, name: &str) -> Result<()> {
-        write_resolver(name)?;
-        println!("Generated resolver: {}", name);
+        let resource = self.resource(name)?;
+        write_resolver(&self.project, &resource, self.crud)?;
+        register_resolver(&self.project, &resource)?;
+        message(format!("Generated resolver: {}", name));
         Ok(())
     }
 
     pub async fn generate_input(&self, name: &str) -> Result<()> {
-        write_input(name)?;
-        println!("Generated input: {}", name);
+        write_input(
+            &self.project,
+            &self.resource(name)?,
+            &self.fields,
+            self.crud,
+        )?;
+        message(format!("Generated input: {}", name));
         Ok(())
     }
 
     pub async fn generate_entity(&self, name: &str) -> Result<()> {
-        write_entity(name)?;
-        println!("Generated entity: {}", name);
+        let resource = self.resource(name)?;
+        write_entity(&self.project, &resource, &self.fields, self.crud)?;
+        register_entity(&self.project, &resource)?;
+        message(format!("Generated entity: {}", name));
         Ok(())
     }
 
     pub async fn generate_dto(&self, name: &str) -> Result<()> {
-        write_spring_dto(name)?;
-        println!("Generated dto: {}", name);
+        write_spring_dto(
+            &self.project,
+            &self.resource(name)?,
+            &self.fields,
+            self.crud()?,
+        )?;
+        message(format!("Generated dto: {}", name));
         Ok(())
     }
 
     pub async fn generate_db_config(&self, name: &str) -> Result<()> {
-        write_db_config(name)?;
-        println!("Generated db config: {}", name);
+        write_db_config(&self.project)?;
+        message(format!("Generated db config: {}", name));
+        Ok(())
+    }
+
+    /// Writes the component with the controller and service generated for `name`, and the server
+    /// file that loads it.
+    pub async fn register_component(&self, name: &str) -> Result<()> {
+        let resource = self.resource(name)?;
+        write_spring_component(&self.project, &resource, true)?;
+        register_spring_component(&self.project, &resource)?;
+        message(format!("Registered {} component in the server", name));
         Ok(())
     }
 
     pub async fn generate_server(&self, name: &str) -> Result<()> {
-        write_spring_server_file(&self.config.database, name)?;
-        println!("Generated server: {}", name);
+        write_spring_server_file(&self.project, Some(&self.resource(name)?))?;
+        message(format!("Generated server: {}", name));
         Ok(())
     }
 
     pub async fn generate_all(&self, name: &str) -> Result<()> {
         self.generate_service(name).await?;
 
-        if self.config.api.to_string() == String::from("rest") {
+        if self.project.config().api == Api::Rest {
             self.generate_dto(name).await?;
             self.generate_controller(name).await?;
             self.generate_model(name).await?;
 
-            match self.config.routing.as_str() {
-                "express" => {
+            match self.project.config().routing {
+                Routing::Express => {
                     self.generate_route(name).await?;
                     // Add route to index file
                     // Add server file
                 }
-                "spring" => {
+                Routing::Spring => {
                     self.generate_component(name).await?;
                     // Add controller to component file
                     // Add component to server file
                     // Add server file
                 }
-                _ => println!("Unknown routing type"),
             }
-        } else if self.config.api.to_string() == String::from("graphql") {
+        }
+
+        if self.project.config().serves(Api::Graphql) {
             self.generate_entity(name).await?;
             self.generate_resolver(name).await?;
             self.generate_input(name).await?;
@@ -150,114 +540,33 @@ impl Generator {
     }
 }
 
-pub fn init_architecture() -> Command<'static> {
-    Command::new("generate")
-        .about("DolphJS CLI")
-        .arg(
-            arg!(-s --service <NAME>)
-                .help("Generates a dolphjs service file")
-                .required(false),
-        )
-        .arg(
-            arg!(-c --controller <NAME>)
-                .help("Generates a dolphjs controller file")
-                .required(false),
-        )
-        .arg(
-            arg!(-r --route <NAME>)
-                .help("Generates a dolphjs routes file")
-                .required(false),
-        )
-        .arg(
-            arg!(-m --model <NAME>)
-                .help("Generates a dolphjs models file")
-                .required(false),
-        )
-        .arg(
-            arg!(-e --entity <NAME>)
-                .help("Generates a dolphjs entity file")
-                .required(false),
-        )
-        .arg(
-            arg!(-i --input <NAME>)
-                .help("Generates a dolphjs input file")
-                .required(false),
-        )
-        .arg(
-            arg!(-d --dto <NAME>)
-                .help("Generates a dolphjs dto file")
-                .required(false),
-        )
-        .arg(
-            arg!(-v --resolver <NAME>)
-                .help("Generates a dolphjs resolver file")
-                .required(false),
-        )
-        .arg(
-            arg!(-y --component <NAME>)
-                .help("Generates a dolphjs spring component file")
-                .required(false),
-        )
-        .arg(
-            arg!(-k --socket <NAME>)
-                .help("Generate a dolphjs socket service and component")
-                .required(false),
-        )
-        .arg(
-            arg!(-a --all <NAME>)
-                .help("Generates all dolphjs files for the named parameter")
-                .required(false),
-        )
-}
+/// Directories below `dir` named `kebab` which hold generated files for that name.
+fn find_component(dir: &Path, kebab: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
 
-pub async fn run_init_architecture(generator: Generator, matches: &ArgMatches) -> Result<()> {
-    if let Some(name) = matches.value_of("controller") {
-        generator.generate_controller(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("service") {
-        generator.generate_service(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("route") {
-        if generator.config.routing == "spring" {
-            println!("Cannot create routes file for spring routing");
-        } else {
-            generator.generate_route(name).await?;
+    let mut found = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if !path.is_dir() {
+            continue;
         }
-    }
-
-    if let Some(name) = matches.value_of("model") {
-        generator.generate_model(name).await?;
-    }
 
-    if let Some(name) = matches.value_of("entity") {
-        generator.generate_entity(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("dto") {
-        generator.generate_dto(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("input") {
-        generator.generate_input(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("resolver") {
-        generator.generate_resolver(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("socket") {
-        generator.generate_socket(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("component") {
-        generator.generate_component(name).await?;
-    }
-
-    if let Some(name) = matches.value_of("all") {
-        generator.generate_all(name).await?;
+        let is_component = path.file_name().is_some_and(|file| file == kebab)
+            && fs::read_dir(&path).is_ok_and(|mut files| {
+                files.any(|file| {
+                    file.is_ok_and(|file| {
+                        file.file_name()
+                            .to_string_lossy()
+                            .starts_with(&format!("{}.", kebab))
+                    })
+                })
+            });
+        if is_component {
+            found.push(path.clone());
+        }
+        found.extend(find_component(&path, kebab));
     }
 
-    Ok(())
+    found
 }
@@ -6,3 +6,25 @@ pub fn is_bin_installed(bin: &str) -> bool {
         Err(_) => false,
     }
 }
+
+/// Runs `<bin> --version` and returns the first version-looking token of its output,
+/// without any leading `v` (e.g. `v20.11.0` -> `20.11.0`).
+pub fn bin_version(bin: &str) -> Option<String> {
+    let output = Command::new(bin).arg("--version").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    stdout
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            let mut parts = token.split('.');
+            parts.next().is_some_and(|p| p.parse::<u64>().is_ok())
+                && parts.next().is_some_and(|p| p.parse::<u64>().is_ok())
+        })
+        .map(|token| token.to_string())
+}
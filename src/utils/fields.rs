@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::error::{DolphError, Result};
+
+use super::ResourceName;
+
+/// The type of a generated field, mapped onto the types of each library that declares it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    String,
+    Number,
+    Boolean,
+    Date,
+}
+
+impl FieldType {
+    pub const VARIANTS: &'static [Self] = &[
+        FieldType::String,
+        FieldType::Number,
+        FieldType::Boolean,
+        FieldType::Date,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Number => "number",
+            FieldType::Boolean => "boolean",
+            FieldType::Date => "date",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|variant| variant.as_str() == value.to_lowercase())
+            .copied()
+    }
+
+    pub fn ts_type(&self) -> &'static str {
+        match self {
+            FieldType::Date => "Date",
+            other => other.as_str(),
+        }
+    }
+
+    pub fn graphql_type(&self) -> &'static str {
+        match self {
+            FieldType::String => "String",
+            FieldType::Number => "Number",
+            FieldType::Boolean => "Boolean",
+            FieldType::Date => "Date",
+        }
+    }
+
+    pub fn mongoose_type(&self) -> &'static str {
+        self.graphql_type()
+    }
+
+    pub fn sequelize_type(&self) -> &'static str {
+        match self {
+            FieldType::String => "DataTypes.STRING",
+            FieldType::Number => "DataTypes.FLOAT",
+            FieldType::Boolean => "DataTypes.BOOLEAN",
+            FieldType::Date => "DataTypes.DATE",
+        }
+    }
+
+    /// The class-validator decorator that checks the type.
+    pub fn validator(&self) -> &'static str {
+        match self {
+            FieldType::String => "IsString",
+            FieldType::Number => "IsNumber",
+            FieldType::Boolean => "IsBoolean",
+            FieldType::Date => "IsDate",
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A field of a generated model, entity, dto or input, e.g. `title:string`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// The property name, in camelCase.
+    pub name: String,
+    pub kind: FieldType,
+}
+
+impl Field {
+    pub fn new(name: &str, kind: FieldType) -> Result<Self> {
+        if name.contains('/') {
+            return Err(DolphError::InvalidName {
+                name: name.to_string(),
+                reason: String::from("field names cannot contain `/`"),
+                suggestion: Some(name.replace('/', "-")),
+            });
+        }
+        let name = ResourceName::parse(name)?;
+
+        Ok(Self {
+            name: name.camel(),
+            kind,
+        })
+    }
+
+    /// Parses a comma separated list of `name:type` pairs, the type defaults to `string`.
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(|field| {
+                let (name, kind) = field.split_once(':').unwrap_or((field, "string"));
+                let kind = FieldType::parse(kind.trim()).ok_or_else(|| {
+                    DolphError::Usage(format!(
+                        "unknown type `{}` for field `{}`, expected one of: {}",
+                        kind.trim(),
+                        name.trim(),
+                        FieldType::VARIANTS
+                            .iter()
+                            .map(FieldType::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                })?;
+                Self::new(name.trim(), kind)
+            })
+            .collect()
+    }
+}
+
+/// The class-validator decorators `fields` need, sorted and deduplicated, together with `extra`.
+pub fn validator_imports(fields: &[Field], extra: &[&'static str]) -> Vec<&'static str> {
+    let mut imports: Vec<&'static str> = fields
+        .iter()
+        .map(|field| field.kind.validator())
+        .chain(extra.iter().copied())
+        .collect();
+    imports.sort_unstable();
+    imports.dedup();
+    imports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, FieldType};
+    use crate::error::DolphError;
+
+    #[test]
+    fn parses_fields() {
+        let fields = Field::parse_list("title, price:number,released-at:Date").unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                Field::new("title", FieldType::String).unwrap(),
+                Field::new("price", FieldType::Number).unwrap(),
+                Field::new("releasedAt", FieldType::Date).unwrap(),
+            ]
+        );
+
+        assert!(matches!(
+            Field::parse_list("price:money"),
+            Err(DolphError::Usage(_))
+        ));
+        assert!(matches!(
+            Field::parse_list("new:string"),
+            Err(DolphError::InvalidName { suggestion: Some(suggestion), .. }) if suggestion == "new-item"
+        ));
+    }
+}
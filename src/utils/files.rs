@@ -0,0 +1,49 @@
+use std::{fs, path::Path};
+
+use crate::error::{DolphError, Result};
+use crate::output::{FileAction, FileChanges};
+
+pub fn ensure_directory_exists(path: &Path) -> Result<()> {
+    if !path.exists() {
+        fs::create_dir_all(path).map_err(|e| DolphError::write(path, e))?;
+    }
+    Ok(())
+}
+
+/// Writes a generated file, recording it in `changes` for the `--json` file listing.
+pub fn write_file(changes: &FileChanges, path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    let action = if path.exists() {
+        FileAction::Modified
+    } else {
+        FileAction::Created
+    };
+
+    fs::write(path, content).map_err(|e| DolphError::write(path, e))?;
+    changes.record(path, action);
+    Ok(())
+}
+
+/// Replaces the file at `path` with `content`, unless it was changed since it was generated as
+/// `previous`. Missing files are written. Returns whether the file now holds `content`.
+pub fn replace_generated(
+    changes: &FileChanges,
+    path: &Path,
+    previous: &str,
+    content: &str,
+) -> Result<bool> {
+    let current = match fs::read_to_string(path) {
+        Ok(current) => current,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::from(previous),
+        Err(e) => return Err(DolphError::write(path, e)),
+    };
+
+    if current == content {
+        return Ok(true);
+    }
+    if current != previous {
+        return Ok(false);
+    }
+
+    write_file(changes, path, content)?;
+    Ok(true)
+}
@@ -0,0 +1,204 @@
+//! English inflection of the lowercase words a [`ResourceName`](super::ResourceName) is made of.
+
+/// Words with the same singular and plural form.
+const UNCOUNTABLE: &[&str] = &[
+    "data",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "media",
+    "metadata",
+    "money",
+    "news",
+    "rice",
+    "series",
+    "sheep",
+    "software",
+    "species",
+    "staff",
+];
+
+/// Singular and plural forms the suffix rules get wrong.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("child", "children"),
+    ("foot", "feet"),
+    ("goose", "geese"),
+    ("half", "halves"),
+    ("hero", "heroes"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("life", "lives"),
+    ("man", "men"),
+    ("mouse", "mice"),
+    ("ox", "oxen"),
+    ("person", "people"),
+    ("potato", "potatoes"),
+    ("quiz", "quizzes"),
+    ("shelf", "shelves"),
+    ("tooth", "teeth"),
+    ("wife", "wives"),
+    ("wolf", "wolves"),
+    ("woman", "women"),
+];
+
+/// Singulars ending in an e that their -es plural looks like it adds, matched as suffixes.
+const E_SINGULARS: &[&str] = &[
+    "avalanche",
+    "cache",
+    "cliche",
+    "headache",
+    "moustache",
+    "niche",
+    "quiche",
+];
+
+/// Singulars ending in -ie, whose plural looks like a -y word's, matched as suffixes.
+const IE_SINGULARS: &[&str] = &[
+    "brownie", "calorie", "cookie", "freebie", "genie", "goodie", "hoodie", "movie", "necktie",
+    "newbie", "prairie", "rookie", "selfie", "smoothie", "zombie",
+];
+
+/// Singulars ending in s, which take -es instead of looking plural already.
+const S_SINGULARS: &[&str] = &[
+    "alias",
+    "apparatus",
+    "atlas",
+    "bias",
+    "bonus",
+    "bus",
+    "campus",
+    "canvas",
+    "census",
+    "circus",
+    "consensus",
+    "corpus",
+    "gas",
+    "iris",
+    "lens",
+    "nexus",
+    "octopus",
+    "plus",
+    "prospectus",
+    "status",
+    "syllabus",
+    "virus",
+];
+
+/// The plural of `word`, words that already look plural are kept as they are.
+pub fn pluralize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) || IRREGULAR.iter().any(|(_, plural)| *plural == word) {
+        return word.to_string();
+    }
+    if let Some((_, plural)) = IRREGULAR.iter().find(|(singular, _)| *singular == word) {
+        return plural.to_string();
+    }
+
+    if let Some(stem) = word.strip_suffix("sis") {
+        format!("{}ses", stem)
+    } else if S_SINGULARS.contains(&word) {
+        format!("{}es", word)
+    } else if word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") {
+        word.to_string()
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        format!("{}es", word)
+    } else if let Some(stem) = word
+        .strip_suffix('y')
+        .filter(|stem| ends_with_consonant(stem))
+    {
+        format!("{}ies", stem)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// The singular of `word`, words that already look singular are kept as they are.
+pub fn singularize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) || IRREGULAR.iter().any(|(singular, _)| *singular == word) {
+        return word.to_string();
+    }
+    if let Some((singular, _)) = IRREGULAR.iter().find(|(_, plural)| *plural == word) {
+        return singular.to_string();
+    }
+
+    if S_SINGULARS.contains(&word) {
+        word.to_string()
+    } else if let Some(stem) = word
+        .strip_suffix("es")
+        .filter(|stem| S_SINGULARS.contains(stem))
+    {
+        stem.to_string()
+    } else if IE_SINGULARS.iter().any(|singular| {
+        word.strip_suffix('s')
+            .is_some_and(|stem| stem.ends_with(singular))
+    }) {
+        word[..word.len() - 1].to_string()
+    } else if let Some(stem) = word
+        .strip_suffix("ies")
+        .filter(|stem| ends_with_consonant(stem))
+    {
+        format!("{}y", stem)
+    } else if let Some(stem) = word.strip_suffix("yses") {
+        format!("{}ysis", stem)
+    } else if E_SINGULARS.iter().any(|singular| {
+        word.strip_suffix('s')
+            .is_some_and(|stem| stem.ends_with(singular))
+    }) {
+        word[..word.len() - 1].to_string()
+    } else if ["sses", "xes", "zes", "ches", "shes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else if word.ends_with('s') && !["ss", "us", "is"].iter().any(|s| word.ends_with(s)) {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+fn ends_with_consonant(stem: &str) -> bool {
+    stem.chars().last().is_some_and(|c| !"aeiou".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pluralize, singularize};
+
+    #[test]
+    fn inflects_words() {
+        let cases = [
+            ("user", "users"),
+            ("category", "categories"),
+            ("status", "statuses"),
+            ("address", "addresses"),
+            ("person", "people"),
+            ("analysis", "analyses"),
+            ("data", "data"),
+            ("house", "houses"),
+            ("cache", "caches"),
+            ("course", "courses"),
+            ("cause", "causes"),
+            ("church", "churches"),
+            ("box", "boxes"),
+            ("movie", "movies"),
+            ("cookie", "cookies"),
+            ("zombie", "zombies"),
+            ("excuse", "excuses"),
+            ("abuse", "abuses"),
+            ("bus", "buses"),
+            ("gas", "gases"),
+            ("alias", "aliases"),
+        ];
+
+        for (singular, plural) in cases {
+            assert_eq!(pluralize(singular), plural, "{}", singular);
+            assert_eq!(pluralize(plural), plural, "{}", plural);
+            assert_eq!(singularize(plural), singular, "{}", plural);
+            assert_eq!(singularize(singular), singular, "{}", singular);
+        }
+    }
+}
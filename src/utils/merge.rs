@@ -0,0 +1,130 @@
+use serde_json::Value;
+
+/// A value in an existing file that differs from the one dolph generates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// The keys leading to the value from the root of the document.
+    pub keys: Vec<String>,
+    pub current: Value,
+    pub generated: Value,
+}
+
+impl Conflict {
+    /// The dotted path of the value, e.g. `scripts.build`.
+    pub fn key(&self) -> String {
+        self.keys.join(".")
+    }
+
+    /// Replaces the current value in `document` with the generated one.
+    pub fn resolve(&self, document: &mut Value) {
+        let mut target = document;
+        for key in &self.keys {
+            target = &mut target[key.as_str()];
+        }
+        *target = self.generated.clone();
+    }
+}
+
+/// Adds the keys of `generated` that `current` is missing, recursing into objects both have.
+/// Values that differ are left as they are and returned.
+pub fn merge_json(current: &mut Value, generated: &Value) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    merge_value(current, generated, &mut Vec::new(), &mut conflicts);
+    conflicts
+}
+
+fn merge_value(
+    current: &mut Value,
+    generated: &Value,
+    keys: &mut Vec<String>,
+    conflicts: &mut Vec<Conflict>,
+) {
+    match (current, generated) {
+        (Value::Object(current), Value::Object(generated)) => {
+            for (key, value) in generated {
+                match current.get_mut(key) {
+                    Some(existing) => {
+                        keys.push(key.clone());
+                        merge_value(existing, value, keys, conflicts);
+                        keys.pop();
+                    }
+                    None => {
+                        current.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (current, generated) if *current != *generated => conflicts.push(Conflict {
+            keys: keys.clone(),
+            current: current.clone(),
+            generated: generated.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Appends the lines of `generated` that `current` is missing, e.g. to a `.gitignore`.
+pub fn merge_lines(current: &str, generated: &str) -> String {
+    let existing: Vec<&str> = current.lines().map(str::trim).collect();
+    let missing: Vec<&str> = generated
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !existing.contains(&line.trim()))
+        .collect();
+
+    let mut merged = current.to_string();
+    if missing.is_empty() {
+        return merged;
+    }
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    for line in missing {
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{merge_json, merge_lines};
+
+    #[test]
+    fn customized_values_are_reported_and_kept() {
+        let mut current = json!({
+            "scripts": { "build": "tsc", "lint": "eslint ." },
+            "include": ["src"]
+        });
+        let generated = json!({
+            "scripts": { "build": "dolph build", "start": "dolph start" },
+            "include": ["src/", ".env"]
+        });
+
+        let conflicts = merge_json(&mut current, &generated);
+
+        let keys: Vec<String> = conflicts.iter().map(|conflict| conflict.key()).collect();
+        assert_eq!(keys, ["include", "scripts.build"]);
+        assert_eq!(current["scripts"]["build"], "tsc");
+        assert_eq!(current["scripts"]["start"], "dolph start");
+        assert_eq!(current["include"], json!(["src"]));
+
+        for conflict in &conflicts {
+            conflict.resolve(&mut current);
+        }
+        assert_eq!(current["scripts"]["build"], "dolph build");
+        assert_eq!(current["scripts"]["lint"], "eslint .");
+        assert_eq!(current["include"], json!(["src/", ".env"]));
+    }
+
+    #[test]
+    fn ignore_rules_are_only_appended_once() {
+        let merged = merge_lines(
+            "node_modules\n# local\n.env.local\n",
+            "node_modules\n.env\n",
+        );
+        assert_eq!(merged, "node_modules\n# local\n.env.local\n.env\n");
+        assert_eq!(merge_lines(&merged, "node_modules\n.env\n"), merged);
+    }
+}
@@ -1,7 +1,20 @@
 mod bin_finder;
-mod capitalize;
+mod fields;
+mod files;
+mod inflection;
+mod merge;
+mod names;
+mod project_root;
 mod read_config;
+mod source;
+mod staging;
 
-pub use bin_finder::is_bin_installed;
-pub use capitalize::capitalize_first_letter;
-pub use read_config::{ensure_directory_exists, read_config};
+pub use bin_finder::{bin_version, is_bin_installed};
+pub use fields::{validator_imports, Field, FieldType};
+pub use files::{ensure_directory_exists, replace_generated, write_file};
+pub use merge::{merge_json, merge_lines, Conflict};
+pub use names::{validate_package_name, ResourceName};
+pub use project_root::{resolve_project_root, CONFIG_FILE_NAME};
+pub use read_config::read_config;
+pub use source::{add_import, add_to_array};
+pub use staging::Staging;
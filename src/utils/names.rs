@@ -0,0 +1,469 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::Naming;
+use crate::error::{DolphError, Result};
+
+use super::inflection::{pluralize, singularize};
+
+/// Words that cannot be used as variable names in the generated code, which are strict mode ES
+/// modules. Contextual keywords such as `type` or `from` are valid names and not listed.
+const RESERVED_WORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Globals that a generated class of the same name would shadow.
+const GLOBAL_CLASSES: &[&str] = &[
+    "Array", "Boolean", "Buffer", "Date", "Error", "Function", "JSON", "Map", "Math", "Number",
+    "Object", "Promise", "Proxy", "Reflect", "RegExp", "Set", "String", "Symbol", "WeakMap",
+    "WeakSet",
+];
+
+/// Node.js core modules, which npm does not allow as package names.
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "module",
+    "net",
+    "os",
+    "path",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "worker_threads",
+    "zlib",
+];
+
+/// A resource name as passed to `generate`, e.g. `users`, `user-profile` or `admin/users`.
+///
+/// Everything before the last `/` is a namespace, each segment becomes a directory. The last
+/// segment is the base identifier, split into lowercase words so it can be rendered in whichever
+/// case a file needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceName {
+    namespace: Vec<String>,
+    words: Vec<String>,
+}
+
+impl ResourceName {
+    /// Parses and validates a name, rejecting anything that would not produce valid TypeScript.
+    pub fn parse(input: &str) -> Result<Self> {
+        let invalid = |reason: &str, suggestion: Option<String>| DolphError::InvalidName {
+            name: input.to_string(),
+            reason: reason.to_string(),
+            suggestion,
+        };
+
+        if input.chars().any(|c| !is_name_char(c)) {
+            let cleaned = sanitize(input);
+            return Err(invalid(
+                "names may only contain letters, digits, `-`, `_` and `/`",
+                Self::parse(&cleaned).ok().map(|_| cleaned),
+            ));
+        }
+
+        let mut segments: Vec<Vec<String>> = input
+            .split('/')
+            .map(split_words)
+            .filter(|words| !words.is_empty())
+            .collect();
+        let words = segments.pop().unwrap_or_default();
+        let name = Self {
+            namespace: segments.iter().map(|words| words.join("-")).collect(),
+            words,
+        };
+
+        if name.words.is_empty() {
+            return Err(invalid("the name is empty", None));
+        }
+
+        if name.words[0].starts_with(|c: char| c.is_ascii_digit()) {
+            let suggestion = name.with_words(
+                std::iter::once(name.words[0].trim_start_matches(|c: char| c.is_ascii_digit()))
+                    .chain(name.words[1..].iter().map(String::as_str)),
+            );
+            return Err(invalid(
+                "identifiers cannot start with a digit",
+                suggestion.map(|name| name.path()),
+            ));
+        }
+
+        if RESERVED_WORDS.contains(&name.camel().as_str()) {
+            return Err(invalid(
+                "it is a reserved word in JavaScript",
+                name.with_suffix("item"),
+            ));
+        }
+
+        if GLOBAL_CLASSES.contains(&name.pascal().as_str()) {
+            return Err(invalid(
+                "its class would shadow a JavaScript global",
+                name.with_suffix("item"),
+            ));
+        }
+
+        Ok(name)
+    }
+
+    /// The same namespace with different base words, if any are left.
+    fn with_words<'a>(&self, words: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let words: Vec<String> = words
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect();
+        (!words.is_empty()).then(|| Self {
+            namespace: self.namespace.clone(),
+            words,
+        })
+    }
+
+    fn with_suffix(&self, suffix: &str) -> Option<String> {
+        self.with_words(
+            self.words
+                .iter()
+                .map(String::as_str)
+                .chain(std::iter::once(suffix)),
+        )
+        .map(|name| name.path())
+    }
+
+    /// A name for the same resource prefixed with its namespace, e.g. `admin/admin-users`, which
+    /// no longer clashes with a `users` resource elsewhere.
+    pub fn qualified(&self) -> Option<String> {
+        let namespace = self.namespace.last()?;
+        self.with_words(
+            namespace
+                .split('-')
+                .chain(self.words.iter().map(String::as_str)),
+        )
+        .map(|name| name.path())
+    }
+
+    /// `UserProfile`, for class names.
+    pub fn pascal(&self) -> String {
+        self.words.iter().map(|word| capitalize(word)).collect()
+    }
+
+    /// `userProfile`, for properties and variables.
+    pub fn camel(&self) -> String {
+        let pascal = self.pascal();
+        let mut chars = pascal.chars();
+        match chars.next() {
+            Some(first) => first.to_lowercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// `user-profile`, for file and directory names.
+    pub fn kebab(&self) -> String {
+        self.words.join("-")
+    }
+
+    /// `user_profile`, for database identifiers.
+    pub fn snake(&self) -> String {
+        self.words.join("_")
+    }
+
+    /// `admin/user-profile`, the path of the resource below a directory such as `components`.
+    pub fn path(&self) -> String {
+        self.namespace
+            .iter()
+            .cloned()
+            .chain(std::iter::once(self.kebab()))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// `admin/user-profiles`, the route the resource is served under.
+    pub fn route_path(&self, naming: Naming) -> String {
+        self.namespace
+            .iter()
+            .cloned()
+            .chain(std::iter::once(self.inflected_words(naming).join("-")))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// `user_profiles`, the table or collection the resource is stored in.
+    pub fn table_name(&self, naming: Naming) -> String {
+        self.inflected_words(naming).join("_")
+    }
+
+    /// The words with the last one inflected according to the project's naming.
+    fn inflected_words(&self, naming: Naming) -> Vec<String> {
+        let mut words = self.words.clone();
+        if let Some(last) = words.last_mut() {
+            *last = match naming {
+                Naming::Plural => pluralize(last),
+                Naming::Singular => singularize(last),
+            };
+        }
+        words
+    }
+
+    /// The directory of the resource below `base`, e.g. `components/admin/user-profile`.
+    pub fn dir(&self, base: &Path) -> PathBuf {
+        base.join(self.path())
+    }
+
+    /// The namespace directories below `base`, for resources that share a flat directory.
+    pub fn namespace_dir(&self, base: &Path) -> PathBuf {
+        self.namespace
+            .iter()
+            .fold(base.to_path_buf(), |dir, segment| dir.join(segment))
+    }
+}
+
+/// Checks `name` against the npm package name rules, `dolph new` uses it as the package name.
+pub fn validate_package_name(name: &str) -> Result<()> {
+    let invalid = |reason: &str| {
+        let suggestion = sanitize(name)
+            .replace('/', "-")
+            .to_lowercase()
+            .trim_start_matches(['.', '_', '-'])
+            .to_string();
+        DolphError::InvalidName {
+            name: name.to_string(),
+            reason: reason.to_string(),
+            suggestion: (!suggestion.is_empty() && suggestion != name).then_some(suggestion),
+        }
+    };
+
+    if name.is_empty() {
+        return Err(invalid("the name is empty"));
+    }
+    if name.len() > 214 {
+        return Err(invalid(
+            "npm package names cannot be longer than 214 characters",
+        ));
+    }
+    if name.starts_with(['.', '_']) {
+        return Err(invalid("npm package names cannot start with `.` or `_`"));
+    }
+    if name != name.to_lowercase() {
+        return Err(invalid("npm package names must be lowercase"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c))
+    {
+        return Err(invalid(
+            "npm package names may only contain lowercase letters, digits, `-`, `.`, `_` and `~`",
+        ));
+    }
+    if NODE_BUILTINS.contains(&name) || ["node_modules", "favicon.ico"].contains(&name) {
+        return Err(invalid("the name is reserved by npm or node"));
+    }
+
+    Ok(())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '/'
+}
+
+/// Turns whitespace into `-` and drops every other character that is not allowed in a name.
+fn sanitize(input: &str) -> String {
+    input
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| is_name_char(*c))
+        .collect()
+}
+
+/// Splits `userProfile`, `user-profile`, `user_profile` or `UserProfile` into lowercase words.
+fn split_words(segment: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = segment.chars().collect();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let starts_word = c.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+            });
+
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_package_name, ResourceName};
+    use crate::config::Naming;
+    use crate::error::DolphError;
+
+    fn suggestion(result: Result<impl std::fmt::Debug, DolphError>) -> Option<String> {
+        match result {
+            Err(DolphError::InvalidName { suggestion, .. }) => suggestion,
+            other => panic!("expected an invalid name, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_resource_names() {
+        assert_eq!(
+            suggestion(ResourceName::parse("class")),
+            Some("class-item".into())
+        );
+        assert_eq!(
+            suggestion(ResourceName::parse("123abc")),
+            Some("abc".into())
+        );
+        assert_eq!(
+            suggestion(ResourceName::parse("my model")),
+            Some("my-model".into())
+        );
+        assert_eq!(
+            suggestion(ResourceName::parse("admin/promise")),
+            Some("admin/promise-item".into())
+        );
+        assert_eq!(suggestion(ResourceName::parse("123")), None);
+
+        assert!(ResourceName::parse("admin/user-profile").is_ok());
+        assert!(ResourceName::parse("classRoom").is_ok());
+        for contextual in [
+            "type",
+            "get",
+            "of",
+            "from",
+            "as",
+            "is",
+            "module",
+            "namespace",
+            "any",
+        ] {
+            assert!(ResourceName::parse(contextual).is_ok(), "{}", contextual);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_package_names() {
+        assert_eq!(
+            suggestion(validate_package_name("MyApp")),
+            Some("myapp".into())
+        );
+        assert_eq!(
+            suggestion(validate_package_name("my app")),
+            Some("my-app".into())
+        );
+        assert_eq!(
+            suggestion(validate_package_name(".app")),
+            Some("app".into())
+        );
+        assert!(validate_package_name("http").is_err());
+
+        assert!(validate_package_name("my-app").is_ok());
+    }
+
+    #[test]
+    fn names_routes_and_tables() {
+        let name = ResourceName::parse("admin/UserProfile").unwrap();
+        assert_eq!(name.route_path(Naming::Plural), "admin/user-profiles");
+        assert_eq!(name.route_path(Naming::Singular), "admin/user-profile");
+        assert_eq!(name.table_name(Naming::Plural), "user_profiles");
+        assert_eq!(name.table_name(Naming::Singular), "user_profile");
+    }
+}
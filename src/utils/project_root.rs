@@ -0,0 +1,133 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::config::ConfigError;
+use crate::error::Result;
+
+pub const CONFIG_FILE_NAME: &str = "dolph_cli.yaml";
+
+/// Overrides project root discovery, e.g. for editor integrations that run `dolph` elsewhere.
+pub const PROJECT_ROOT_ENV: &str = "DOLPH_PROJECT_ROOT";
+
+/// Walks up from `start` to the nearest directory that contains a `dolph_cli.yaml`.
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(CONFIG_FILE_NAME).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Resolves the project root from `--cwd`, then `DOLPH_PROJECT_ROOT`, then the current directory.
+///
+/// `--cwd` and the current directory are starting points for the upward search, while
+/// `DOLPH_PROJECT_ROOT` must point at the project root itself.
+pub fn resolve_project_root(cwd: Option<&str>) -> Result<PathBuf> {
+    resolve_from(
+        cwd.map(Path::new),
+        std::env::var_os(PROJECT_ROOT_ENV).as_deref(),
+        &std::env::current_dir()?,
+    )
+}
+
+/// [`resolve_project_root`] with the environment passed in.
+fn resolve_from(
+    cwd: Option<&Path>,
+    env_root: Option<&OsStr>,
+    current_dir: &Path,
+) -> Result<PathBuf> {
+    let start = match cwd {
+        Some(cwd) => cwd.canonicalize()?,
+        None => match env_root {
+            Some(root) if !root.is_empty() => {
+                let root = Path::new(root).canonicalize()?;
+                if !root.join(CONFIG_FILE_NAME).is_file() {
+                    return Err(ConfigError::InvalidRoot { root }.into());
+                }
+                return Ok(root);
+            }
+            _ => current_dir.to_path_buf(),
+        },
+    };
+
+    find_project_root(&start).ok_or_else(|| ConfigError::NotFound { start }.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::{resolve_from, CONFIG_FILE_NAME};
+    use crate::config::ConfigError;
+    use crate::error::DolphError;
+
+    /// A project at `project` with a nested `project/src/components`, and a directory `other`
+    /// next to it without a config.
+    fn layout() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("project/src/components")).unwrap();
+        fs::create_dir(dir.path().join("other")).unwrap();
+        fs::write(dir.path().join("project").join(CONFIG_FILE_NAME), "").unwrap();
+        dir
+    }
+
+    fn path(dir: &TempDir, relative: &str) -> std::path::PathBuf {
+        dir.path().join(relative).canonicalize().unwrap()
+    }
+
+    #[test]
+    fn walks_up_from_the_current_directory() {
+        let dir = layout();
+        let nested = path(&dir, "project/src/components");
+
+        let root = resolve_from(None, None, &nested).unwrap();
+
+        assert_eq!(root, path(&dir, "project"));
+    }
+
+    #[test]
+    fn cwd_wins_over_the_env_override_and_current_directory() {
+        let dir = layout();
+        let other = path(&dir, "other");
+        let nested = path(&dir, "project/src");
+
+        let root = resolve_from(Some(&nested), Some(other.as_os_str()), &other).unwrap();
+
+        assert_eq!(root, path(&dir, "project"));
+    }
+
+    #[test]
+    fn env_override_wins_over_the_current_directory() {
+        let dir = layout();
+        let project = path(&dir, "project");
+
+        let root = resolve_from(None, Some(project.as_os_str()), &path(&dir, "other")).unwrap();
+        assert_eq!(root, project);
+
+        // An empty variable counts as unset
+        let root = resolve_from(None, Some("".as_ref()), &project.join("src")).unwrap();
+        assert_eq!(root, project);
+    }
+
+    #[test]
+    fn env_override_must_point_at_a_project_root() {
+        let dir = layout();
+
+        // The override is not searched upwards, unlike --cwd and the current directory
+        let nested = path(&dir, "project/src");
+        let result = resolve_from(None, Some(nested.as_os_str()), Path::new("/"));
+        assert!(matches!(
+            result,
+            Err(DolphError::Config(ConfigError::InvalidRoot { root })) if root == nested
+        ));
+
+        let other = path(&dir, "other");
+        let result = resolve_from(None, None, &other);
+        assert!(matches!(
+            result,
+            Err(DolphError::Config(ConfigError::NotFound { start })) if start == other
+        ));
+    }
+}
@@ -1,30 +1,28 @@
-use std::{error::Error, fs, path::Path};
+use std::fs;
+use std::path::Path;
 
-use crate::properties::DefaultConfig;
+use crate::config::{parse_config, ConfigError, DefaultConfig};
+use crate::error::Result;
+use crate::output::{message, FileChanges};
 
-pub fn read_config() -> Result<DefaultConfig, Box<dyn Error>> {
-    // Todo: return to this before pushing to NPM
-    let config_path = Path::new("dolph_cli.yaml");
+use super::{write_file, CONFIG_FILE_NAME};
 
-    // let app_path = Path::new("app");
-    // let config_path = app_path.join("dolph_cli.yaml");
+/// Reads and validates the `dolph_cli.yaml` of the project at `root`. An outdated config is
+/// migrated in place and recorded in `changes`.
+pub fn read_config(root: &Path, changes: &FileChanges) -> Result<DefaultConfig> {
+    let config_path = root.join(CONFIG_FILE_NAME);
 
-    let yaml_content = fs::read_to_string(config_path)?;
+    let yaml_content = fs::read_to_string(&config_path).map_err(ConfigError::Unreadable)?;
 
-    let config_content = yaml_content
-        .lines()
-        .filter(|line| !line.trim_start().starts_with("#"))
-        .collect::<Vec<&str>>()
-        .join("\n");
+    let (config, migrated) = parse_config(&yaml_content)?;
 
-    let config: DefaultConfig = serde_yaml::from_str(&config_content)?;
+    if migrated {
+        write_file(changes, &config_path, config.to_yaml()?)?;
+        message(format!(
+            "Migrated dolph_cli.yaml to config version {}. ✨",
+            config.version
+        ));
+    }
 
     Ok(config)
 }
-
-pub fn ensure_directory_exists(path: &Path) -> Result<(), Box<dyn Error>> {
-    if !path.exists() {
-        fs::create_dir_all(path)?;
-    }
-    Ok(())
-}
@@ -0,0 +1,75 @@
+//! Small edits of generated TypeScript files, which are line based and only understand the
+//! layout the writers produce.
+
+/// Adds `import { name } from "module";` after the last import, unless `name` is imported already.
+pub fn add_import(content: &str, name: &str, module: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    if let Some(line) = lines.iter_mut().find(|line| {
+        line.starts_with("import {") && line.ends_with(&format!("from \"{}\";", module))
+    }) {
+        // One-line import of the same module, add the name to it
+        let names_end = line.find('}').unwrap_or(line.len());
+        let mut names: Vec<&str> = line["import {".len()..names_end]
+            .split(',')
+            .map(str::trim)
+            .filter(|existing| !existing.is_empty())
+            .collect();
+        if !names.contains(&name) {
+            names.push(name);
+            names.sort_unstable();
+            *line = format!("import {{ {} }} from \"{}\";", names.join(", "), module);
+        }
+    } else if !is_imported(&lines, name) {
+        // After the last import, so the import block stays together
+        let position = lines
+            .iter()
+            .rposition(|line| line.starts_with("import") || line.starts_with("} from"))
+            .map_or(0, |index| index + 1);
+        lines.insert(
+            position,
+            format!("import {{ {} }} from \"{}\";", name, module),
+        );
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+/// Adds `item` to the array literal opened by `declaration`, e.g. `entities: [`, unless it is listed
+/// already. Returns `None` when `content` has no such array.
+pub fn add_to_array(content: &str, declaration: &str, item: &str) -> Option<String> {
+    let open = content.find(declaration)? + declaration.len();
+    let close = content[open..].find(']')? + open;
+
+    let mut items: Vec<&str> = content[open..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|existing| !existing.is_empty())
+        .collect();
+    if !items.contains(&item) {
+        items.push(item);
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &content[..open],
+        items.join(", "),
+        &content[close..]
+    ))
+}
+
+fn is_imported(lines: &[String], name: &str) -> bool {
+    let mut in_import = false;
+    lines.iter().any(|line| {
+        if line.starts_with("import") {
+            in_import = !line.contains(" from ");
+        } else if line.starts_with("} from") {
+            in_import = false;
+        } else if !in_import {
+            return false;
+        }
+
+        line.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$')
+            .any(|word| word == name)
+    })
+}
@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DolphError, Result};
+
+/// A directory a new project is written to before it is moved into place, so a failed
+/// `dolph new` leaves nothing behind. It is removed when dropped without being committed.
+#[derive(Debug)]
+pub struct Staging {
+    dir: PathBuf,
+    committed: bool,
+}
+
+impl Staging {
+    /// Creates the staging directory in `parent`. The top-level files of `seed`, the directory the
+    /// project ends up in when it exists already, are copied in so writers see them.
+    pub fn new(parent: &Path, seed: Option<&Path>) -> Result<Self> {
+        let dir = parent.join(format!(".dolph-new-{}", std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| DolphError::write(&dir, e))?;
+        }
+        fs::create_dir(&dir).map_err(|e| DolphError::write(&dir, e))?;
+        let staging = Self {
+            dir,
+            committed: false,
+        };
+
+        if let Some(seed) = seed {
+            for entry in fs::read_dir(seed)?.flatten() {
+                let path = entry.path();
+                if path.is_file() {
+                    let target = staging.dir.join(entry.file_name());
+                    fs::copy(&path, &target).map_err(|e| DolphError::write(&target, e))?;
+                }
+            }
+        }
+
+        Ok(staging)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Moves the staged project to `root`. A missing `root` is created with a single rename,
+    /// otherwise only the files that differ from those in `root` are moved into it. When a move
+    /// fails, the files moved so far are removed and the ones they replaced restored.
+    pub fn commit(mut self, root: &Path) -> Result<()> {
+        if !root.exists() {
+            fs::rename(&self.dir, root).map_err(|e| DolphError::write(root, e))?;
+            self.committed = true;
+            return Ok(());
+        }
+
+        let backup = self.dir.with_extension("backup");
+        let mut moved = Moved::default();
+        let result = self.move_into(root, &backup, &mut moved);
+        if result.is_err() {
+            moved.roll_back();
+        }
+        let _ = fs::remove_dir_all(&backup);
+        result
+    }
+
+    fn move_into(&self, root: &Path, backup: &Path, moved: &mut Moved) -> Result<()> {
+        let (dirs, files) = entries(&self.dir);
+
+        for staged in dirs {
+            let target = root.join(staged.strip_prefix(&self.dir).unwrap());
+            if !target.exists() {
+                fs::create_dir(&target).map_err(|e| DolphError::write(&target, e))?;
+                moved.dirs.push(target);
+            }
+        }
+
+        for staged in files {
+            let relative = staged.strip_prefix(&self.dir).unwrap();
+            let target = root.join(relative);
+
+            if target.is_file() && fs::read(&target).ok() == fs::read(&staged).ok() {
+                continue;
+            }
+
+            if target.exists() {
+                let saved = backup.join(relative);
+                fs::create_dir_all(saved.parent().unwrap())
+                    .map_err(|e| DolphError::write(&saved, e))?;
+                fs::rename(&target, &saved).map_err(|e| DolphError::write(&target, e))?;
+                moved.replaced.push((target.clone(), saved));
+            }
+
+            fs::rename(&staged, &target).map_err(|e| DolphError::write(&target, e))?;
+            moved.files.push(target);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+/// What [`Staging::commit`] changed in an existing directory, to undo it.
+#[derive(Default)]
+struct Moved {
+    files: Vec<PathBuf>,
+    replaced: Vec<(PathBuf, PathBuf)>,
+    dirs: Vec<PathBuf>,
+}
+
+impl Moved {
+    fn roll_back(self) {
+        for file in self.files.iter().rev() {
+            let _ = fs::remove_file(file);
+        }
+        for (target, saved) in self.replaced.iter().rev() {
+            let _ = fs::rename(saved, target);
+        }
+        // Parents were created before their children
+        for dir in self.dirs.iter().rev() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+/// The directories and files below `dir`, each sorted by path so parents come first.
+fn entries(dir: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for path in fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+        {
+            if path.is_dir() {
+                dirs.push(path.clone());
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    dirs.sort();
+    files.sort();
+    (dirs, files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::Staging;
+
+    #[test]
+    fn dropped_staging_leaves_nothing_behind() {
+        let base = TempDir::new().unwrap();
+
+        let staging = Staging::new(base.path(), None).unwrap();
+        fs::create_dir(staging.path().join("src")).unwrap();
+        fs::write(staging.path().join("src/server.ts"), "").unwrap();
+        drop(staging);
+
+        assert_eq!(fs::read_dir(base.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn commit_moves_a_new_project_into_place() {
+        let base = TempDir::new().unwrap();
+        let root = base.path().join("app");
+
+        let staging = Staging::new(base.path(), None).unwrap();
+        fs::create_dir(staging.path().join("tests")).unwrap();
+        fs::write(staging.path().join("package.json"), "{}").unwrap();
+        staging.commit(&root).unwrap();
+
+        assert!(root.join("tests").is_dir());
+        assert!(root.join("package.json").is_file());
+        assert_eq!(fs::read_dir(base.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn commit_keeps_nested_files_of_an_existing_directory() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        fs::create_dir_all(root.join("src/components/legacy")).unwrap();
+        fs::write(root.join("src/components/legacy/legacy.ts"), "keep me").unwrap();
+
+        let staging = Staging::new(root, Some(root)).unwrap();
+        fs::create_dir_all(staging.path().join("src/components")).unwrap();
+        fs::write(staging.path().join("src/server.ts"), "").unwrap();
+        staging.commit(root).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("src/components/legacy/legacy.ts")).unwrap(),
+            "keep me"
+        );
+        assert!(root.join("src/server.ts").is_file());
+        let mut entries: Vec<String> = fs::read_dir(root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["src"]);
+    }
+
+    #[test]
+    fn failed_commit_restores_an_existing_directory() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        fs::write(root.join("package.json"), "{ \"name\": \"mine\" }").unwrap();
+        fs::write(root.join("notes.md"), "keep me").unwrap();
+        // A file where the project needs a directory makes the last move fail
+        fs::write(root.join("tests"), "").unwrap();
+
+        let staging = Staging::new(root, Some(root)).unwrap();
+        fs::write(staging.path().join("package.json"), "{}").unwrap();
+        fs::create_dir(staging.path().join("src")).unwrap();
+        fs::write(staging.path().join("src/server.ts"), "").unwrap();
+        fs::remove_file(staging.path().join("tests")).unwrap();
+        fs::create_dir(staging.path().join("tests")).unwrap();
+        fs::write(staging.path().join("tests/app.spec.ts"), "").unwrap();
+
+        assert!(staging.commit(root).is_err());
+
+        let mut entries: Vec<String> = fs::read_dir(root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["notes.md", "package.json", "tests"]);
+        assert_eq!(
+            fs::read_to_string(root.join("package.json")).unwrap(),
+            "{ \"name\": \"mine\" }"
+        );
+    }
+}
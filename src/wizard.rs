@@ -0,0 +1,107 @@
+//! The interactive `dolph generate`, run when no schematic flag is given on a terminal.
+
+use dialoguer::{Confirm, Input, Select};
+use std::io::IsTerminal;
+
+use crate::error::Result;
+use crate::init::prompt_theme;
+use crate::output::is_json;
+use crate::properties::{Generator, Schematic};
+use crate::utils::{Field, FieldType, ResourceName};
+
+/// Whether prompts can be shown, i.e. stdin and stdout are a terminal and no JSON is expected.
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal() && !is_json()
+}
+
+/// Asks for the schematic, name, fields, registration and the controller to apply middlewares
+/// to, then generates with `generator`.
+pub async fn run_generate_wizard(generator: &Generator) -> Result<()> {
+    let theme = prompt_theme();
+    let config = generator.project().config();
+
+    let schematics = Schematic::available(config);
+    let schematic = schematics[Select::with_theme(&theme)
+        .with_prompt("What do you want to generate?")
+        .default(0)
+        .items(&schematics)
+        .interact()?];
+
+    let name: String = Input::with_theme(&theme)
+        .with_prompt("What should it be called?")
+        .validate_with(|input: &String| {
+            if schematic.is_shared() {
+                return ResourceName::parse(input)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+            }
+            generator
+                .resource(input)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+        .interact_text()?;
+
+    let mut fields = Vec::new();
+    if schematic.has_fields() {
+        loop {
+            let field_name: String = Input::with_theme(&theme)
+                .with_prompt("Add a field (leave empty to finish)")
+                .allow_empty(true)
+                .validate_with(|input: &String| {
+                    if input.is_empty() {
+                        return Ok(());
+                    }
+                    Field::new(input, FieldType::String)
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                })
+                .interact_text()?;
+            if field_name.is_empty() {
+                break;
+            }
+
+            let kind = FieldType::VARIANTS[Select::with_theme(&theme)
+                .with_prompt(format!("What is the type of `{}`?", field_name))
+                .default(0)
+                .items(FieldType::VARIANTS)
+                .interact()?];
+            fields.push(Field::new(&field_name, kind)?);
+        }
+    }
+
+    let register = schematic.can_register(config)
+        && Confirm::with_theme(&theme)
+            .with_prompt("Register it in its component and the server?")
+            .default(true)
+            .interact()?;
+
+    let apply_to = if schematic.can_apply() {
+        let target: String = Input::with_theme(&theme)
+            .with_prompt("Apply it to a controller? (`<controller>` or `<controller>:<handler>`, leave empty to skip)")
+            .allow_empty(true)
+            .validate_with(|input: &String| {
+                if input.is_empty() {
+                    return Ok(());
+                }
+                generator
+                    .clone()
+                    .with_apply_to(Some(input.clone()))
+                    .apply_target()
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            })
+            .interact_text()?;
+        (!target.is_empty()).then_some(target)
+    } else {
+        None
+    };
+
+    generator
+        .clone()
+        .with_fields(fields)
+        .with_register(register)
+        .with_apply_to(apply_to)
+        .generate(schematic, &name)
+        .await
+}
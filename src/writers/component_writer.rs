@@ -1,36 +1,57 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_spring_component(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_spring_component(
+    project: &Project,
+    name: &ResourceName,
+    register: bool,
+) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let specific_component_path = component_path.join(format!("{}", name));
+    let specific_component_path = name.dir(&component_path);
 
     ensure_directory_exists(&specific_component_path)?;
 
-    let index_path = specific_component_path.join(format!("{}.component.ts", name));
-
-    let capitalized_name = capitalize_first_letter(name);
+    let index_path = specific_component_path.join(format!("{}.component.ts", name.kebab()));
+
+    let capitalized_name = name.pascal();
+    let file_name = name.kebab();
+
+    // Only what has been generated so far can be registered
+    let registered = |kind: &str| {
+        register
+            && specific_component_path
+                .join(format!("{}.{}.ts", file_name, kind))
+                .exists()
+    };
+    let controller = registered("controller").then(|| format!("{capitalized_name}Controller"));
+    let service = registered("service").then(|| format!("{capitalized_name}Service"));
+
+    let mut imports = String::new();
+    if let Some(controller) = &controller {
+        imports.push_str(&format!(
+            "\nimport {{ {controller} }} from \"./{file_name}.controller\";"
+        ));
+    }
+    if let Some(service) = &service {
+        imports.push_str(&format!(
+            "\nimport {{ {service} }} from \"./{file_name}.service\";"
+        ));
+    }
 
     let content = format!(
-        r#"import {{ Component }} from "@dolphjs/dolph/decorators";
+        r#"import {{ Component }} from "@dolphjs/dolph/decorators";{imports}
 
-@Component({{ controllers: [], services: [] }})
-export class {capitalized_name}Component {{}};"#
+@Component({{ controllers: [{controllers}], services: [{services}] }})
+export class {capitalized_name}Component {{}};"#,
+        controllers = controller.unwrap_or_default(),
+        services = service.unwrap_or_default(),
     );
 
-    match fs::write(&index_path, content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing component file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, content)
 }
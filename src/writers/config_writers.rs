@@ -1,137 +1,348 @@
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::error::Error;
+use crate::config::{Database, Routing};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::utils::capitalize_first_letter;
+use crate::error::{DolphError, Result};
+use crate::output::message;
+use crate::project::Project;
+use crate::utils::{add_import, merge_json, merge_lines, write_file, Conflict, ResourceName};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-#[derive(Debug, Clone, Copy)]
-pub enum Database {
-    MySQL,
-    MongoDB,
-    PostgreSQL,
-    None,
+/// What happens to the values of an existing package.json, tsconfig.json or .swcrc that differ
+/// from the generated ones.
+#[derive(Clone, Copy)]
+pub enum MergePolicy<'a> {
+    /// The existing values are kept.
+    Keep,
+    /// The existing values are replaced when the callback, given a question, answers yes.
+    Confirm(&'a dyn Fn(&str) -> Result<bool>),
 }
 
-impl Database {
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "mysql" => Some(Database::MySQL),
-            "mongo" => Some(Database::MongoDB),
-            "other" => Some(Database::PostgreSQL),
-            "postgresql" => Some(Database::None),
-            _ => None,
+impl MergePolicy<'_> {
+    pub(super) fn confirm(&self, prompt: &str) -> Result<bool> {
+        match self {
+            MergePolicy::Keep => Ok(false),
+            MergePolicy::Confirm(confirm) => confirm(prompt),
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct DefaultDolphConfig {
-    pub jsonLimit: String,
+    #[serde(rename = "jsonLimit")]
+    pub json_limit: String,
     pub port: String,
 }
 
-pub fn find_base_directory() -> Option<PathBuf> {
-    let root_dir = std::env::current_dir().ok()?;
-    let possible_dirs = vec!["src"];
+/// The server entry of the project, `server.ts` or `server.js` depending on the language.
+pub(super) fn server_path(project: &Project) -> Result<PathBuf> {
+    Ok(project
+        .src_dir()?
+        .join(project.config().language.server_file()))
+}
 
-    for dir in possible_dirs {
-        let path = root_dir.join(dir);
-        if path.exists() {
-            return Some(path);
-        }
+/// Writes the server entry, registering the component `name` when one is given.
+pub fn write_spring_server_file(project: &Project, name: Option<&ResourceName>) -> Result<()> {
+    let database = project.config().database;
+    let index_path = server_path(project)?;
+
+    let mut imports = vec![r#"import { DolphFactory } from "@dolphjs/dolph";"#.to_string()];
+    let mut components = String::new();
+
+    if let Some(name) = name {
+        let capitalized_name = name.pascal();
+        let component_path = name.path();
+        let file_name = name.kebab();
+        imports.push(format!(
+            r#"import {{ {capitalized_name}Component }} from "./components/{component_path}/{file_name}.component";"#
+        ));
+        components = format!("{capitalized_name}Component");
     }
-    None
-}
 
-pub fn write_spring_server_file(db: &str, name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+    let mut other_file_content = format!("const dolph = new DolphFactory([{components}]);\n");
 
-    let index_path = base_directory.join("server.ts");
-    let database = Database::from_str(db).ok_or_else(|| "Invalid database type")?;
+    if database == Database::MySQL {
+        imports.push(
+            r#"import { sequelizeInstance } from "@/shared/configs/db.config";
+import { autoInitMySql } from "@dolphjs/dolph/packages";"#
+                .to_string(),
+        );
+        other_file_content.push_str("autoInitMySql(sequelizeInstance);\n\n");
+    }
 
-    let capitalized_name = capitalize_first_letter(name);
+    other_file_content.push_str("dolph.start();");
 
-    // Generate import statement
-    let import_statement = match (database.clone(), name.is_empty()) {
-        (Database::MySQL, false) => format!(r#"import {{ DolphFactory }} from "@dolphjs/dolph";"#),
-        (Database::MySQL, true) => r#"import { DolphFactory } from "@dolphjs/dolph";
-import { sequelizeInstance } from "@/shared/configs/db.config";
-import { autoInitMySql } from "@dolphjs/dolph/packages";"#
-            .to_string(),
-        (Database::MongoDB, false) => {
-            format!(r#"import {{ DolphFactory }} from "@dolphjs/dolph";"#)
+    let import_statement = imports.join("\n");
+
+    let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
+
+    write_file(project.changes(), &index_path, file_content)
+}
+
+/// Initializes the sequelize instance in `server.ts` when the project uses mysql, and removes the
+/// initialization for other databases.
+pub fn update_server_database(project: &Project) -> Result<()> {
+    let server_path = server_path(project)?;
+    let Ok(content) = fs::read_to_string(&server_path) else {
+        return Ok(());
+    };
+
+    let init = "autoInitMySql(sequelizeInstance);";
+    let imports = [
+        ("sequelizeInstance", "@/shared/configs/db.config"),
+        ("autoInitMySql", "@dolphjs/dolph/packages"),
+    ];
+
+    let updated = if project.config().database == Database::MySQL {
+        if content.contains(init) {
+            return Ok(());
         }
-        (Database::None, false) => format!(r#"import {{ DolphFactory }} from "@dolphjs/dolph";"#),
-        (Database::None, true) => format!(
-            r#"import {{ DolphFactory }} from "@dolphjs/dolph";
-import {{ {capitalized_name}Component }} from "./components/{name}/{name}.component.ts";"#
-        ),
-        (Database::PostgreSQL, false) => {
-            format!(r#"import {{ DolphFactory }} from "@dolphjs/dolph";"#)
+        let content = imports
+            .iter()
+            .fold(content.clone(), |content, (name, module)| {
+                add_import(&content, name, module)
+            });
+        // Before the line that starts the server, which is indented once the data source of
+        // graphql has to be initialized first
+        match content.find("dolph.start();") {
+            Some(start) => {
+                let line_start = content[..start].rfind('\n').map_or(0, |index| index + 1);
+                let indent = &content[line_start..start];
+                format!(
+                    "{}{}{}\n\n{}",
+                    &content[..line_start],
+                    indent,
+                    init,
+                    &content[line_start..]
+                )
+            }
+            None => format!("{}\n{}\n", content.trim_end(), init),
         }
-        (Database::PostgreSQL, true) => format!(
-            r#"import {{ DolphFactory }} from "@dolphjs/dolph";
-import {{ {capitalized_name}Component }} from "./components/{name}/{name}.component.ts";"#
-        ),
-        (Database::MongoDB, true) => {
-            r#"import { DolphFactory } from "@dolphjs/dolph";"#.to_string()
+    } else {
+        let generated: Vec<String> = imports
+            .iter()
+            .map(|(name, module)| format!("import {{ {} }} from \"{}\";", name, module))
+            .chain([init.to_string()])
+            .collect();
+
+        let mut lines: Vec<&str> = Vec::new();
+        let mut removed_init = false;
+        for line in content.lines() {
+            if removed_init && line.trim().is_empty() {
+                removed_init = false;
+                continue;
+            }
+            removed_init = line.trim() == init;
+            if !generated.iter().any(|generated| generated == line.trim()) {
+                lines.push(line);
+            }
         }
+        format!("{}\n", lines.join("\n"))
     };
 
-    // Generate other file content
-    let other_file_content = match (database.clone(), name.is_empty()) {
-        (Database::MySQL, false) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-autoInitMySql(sequelizeInstance);
+    if updated != content {
+        write_file(project.changes(), &server_path, updated)?;
+    }
+    Ok(())
+}
 
-dolph.start();"#
-        ),
-        (Database::MySQL, true) => r#"const dolph = new DolphFactory([]);
-autoInitMySql(sequelizeInstance);
-
-dolph.start();"#
-            .to_string(),
-        (Database::MongoDB, false) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-dolph.start();"#
-        ),
-        (Database::None, false) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-dolph.start();"#
-        ),
-        (Database::None, true) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-dolph.start();"#
-        ),
-        (Database::PostgreSQL, false) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-dolph.start();"#
-        ),
-        (Database::PostgreSQL, true) => format!(
-            r#"const dolph = new DolphFactory([{capitalized_name}Component]);
-dolph.start();"#
+/// Adds the component `name` to the `DolphFactory` in `server.ts`, keeping the components that
+/// are registered already.
+pub fn register_spring_component(project: &Project, name: &ResourceName) -> Result<()> {
+    let capitalized_name = name.pascal();
+    let module = format!("./components/{}/{}.component", name.path(), name.kebab());
+    let component = format!("{capitalized_name}Component");
+
+    edit_server_factory(project, &component, &[(&component, &module)], |call| {
+        if !call.components.split(',').any(|c| c.trim() == component) {
+            if !call.components.trim().is_empty() {
+                call.components.push_str(", ");
+            }
+            call.components.push_str(&component);
+        }
+    })
+}
+
+/// Passes the socket component `name` to the `DolphFactory` in `server.ts`. A factory only takes
+/// one socket component, so a previously registered one is replaced.
+pub fn register_spring_socket(project: &Project, name: &ResourceName) -> Result<()> {
+    let capitalized_name = name.pascal();
+    let module = format!(
+        "{}/{}.socket.component",
+        name.namespace_dir(Path::new("./shared/socket")).display(),
+        name.kebab()
+    );
+    let socket_component = format!("{capitalized_name}SocketComponent");
+
+    edit_server_factory(
+        project,
+        &socket_component,
+        &[(&socket_component, &module)],
+        |call| {
+            if let Some(previous) = call.socket.as_ref().filter(|s| **s != socket_component) {
+                message(format!(
+                    "Replaced the socket component {} in {}, dolph runs a single one",
+                    previous,
+                    project.config().language.server_file()
+                ));
+            }
+            call.socket = Some(socket_component.clone());
+        },
+    )
+}
+
+/// The options the `DolphFactory` serves the graphql schema with.
+const GRAPHQL_OPTIONS: &str = "{ graphql: true, schema: schema(), context }";
+
+/// Serves the graphql schema of `setup.ts` from the `DolphFactory` in `server.ts`, next to its
+/// components, and starts the server once the data source is initialized.
+pub fn register_graphql_server(project: &Project) -> Result<()> {
+    edit_server_factory(
+        project,
+        "the graphql schema",
+        &[
+            ("context", "./setup"),
+            ("schema", "./setup"),
+            ("AppDataSource", "./shared/configs/data_source"),
+            ("logger", "@dolphjs/dolph/utilities"),
+        ],
+        |call| call.options = Some(String::from(GRAPHQL_OPTIONS)),
+    )?;
+
+    let server_path = server_path(project)?;
+    let content =
+        fs::read_to_string(&server_path).map_err(|e| DolphError::write(&server_path, e))?;
+    // Left alone when the factory could not be edited or the data source is initialized already
+    let start = "dolph.start();";
+    if !content.contains(GRAPHQL_OPTIONS)
+        || content.contains("AppDataSource.initialize()")
+        || !content.contains(start)
+    {
+        return Ok(());
+    }
+
+    let started = content.replacen(
+        start,
+        &format!(
+            "AppDataSource.initialize()\n  .then(() => {{\n    {start}\n  }})\n  .catch((err) => logger.error(`[Dolph Error]:`, err));"
         ),
-        (Database::MongoDB, true) => r#"const dolph = new DolphFactory([]);
-dolph.start();"#
-            .to_string(),
+        1,
+    );
+    write_file(project.changes(), &server_path, started)
+}
+
+/// The arguments of `new DolphFactory([components], socket, options)`.
+struct FactoryCall {
+    components: String,
+    socket: Option<String>,
+    options: Option<String>,
+}
+
+/// Rewrites the `new DolphFactory([components], socket, options)` call of `server.ts` with
+/// `edit`, and adds `imports`. `what` names what is registered, for when there is no such call.
+fn edit_server_factory(
+    project: &Project,
+    what: &str,
+    imports: &[(&str, &str)],
+    edit: impl FnOnce(&mut FactoryCall),
+) -> Result<()> {
+    let server_path = server_path(project)?;
+    if !server_path.exists() {
+        write_spring_server_file(project, None)?;
+    }
+    let content =
+        fs::read_to_string(&server_path).map_err(|e| DolphError::write(&server_path, e))?;
+
+    let Some((before, mut call, after)) = split_factory_call(&content) else {
+        message(format!(
+            "Could not find `new DolphFactory([...])` in {}, register {} there manually",
+            project.config().language.server_file(),
+            what
+        ));
+        return Ok(());
     };
 
-    let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
+    edit(&mut call);
+
+    let arguments: Vec<String> = [
+        Some(format!("[{}]", call.components)),
+        call.socket,
+        call.options,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let content = format!(
+        "{}new DolphFactory({}){}",
+        before,
+        arguments.join(", "),
+        after
+    );
+
+    let content = imports.iter().fold(content, |content, (name, module)| {
+        add_import(&content, name, module)
+    });
+    write_file(project.changes(), &server_path, content)
+}
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing server file: {}", e));
-            Err(Box::new(e))
+/// Splits `server.ts` around its factory call into the text before, the arguments of the call,
+/// and the text after.
+fn split_factory_call(content: &str) -> Option<(&str, FactoryCall, &str)> {
+    let start = content.find("new DolphFactory(")?;
+    let arguments_start = start + "new DolphFactory(".len();
+
+    // Arguments are split on the commas outside of brackets, up to the parenthesis closing the call
+    let mut depth = 0;
+    let mut arguments = Vec::new();
+    let mut argument_start = arguments_start;
+    let mut end = None;
+    for (index, c) in content[arguments_start..].char_indices() {
+        let index = arguments_start + index;
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                arguments.push(content[argument_start..index].trim());
+                end = Some(index);
+                break;
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(content[argument_start..index].trim());
+                argument_start = index + 1;
+            }
+            _ => {}
         }
     }
+    let end = end?;
+
+    let mut arguments = arguments
+        .into_iter()
+        .filter(|argument| !argument.is_empty());
+    let components = arguments
+        .next()?
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .trim()
+        .to_string();
+    let (options, socket): (Vec<&str>, Vec<&str>) =
+        arguments.partition(|argument| argument.starts_with('{'));
+
+    Some((
+        &content[..start],
+        FactoryCall {
+            components,
+            socket: socket.first().map(|socket| socket.to_string()),
+            options: options.first().map(|options| options.to_string()),
+        },
+        &content[end + 1..],
+    ))
 }
 
-pub fn write_swcrc(is_spring: bool) -> Result<(), Box<dyn Error>> {
+pub fn write_swcrc(project: &Project, merge: MergePolicy) -> Result<()> {
+    let is_spring = project.config().routing == Routing::Spring;
     // Implementation for writing .swcrc file
-    let root_dir = get_root_directory()?;
+    let root_dir = project.root();
     let file_path = root_dir.join(".swcrc");
 
     let paths = if !is_spring {
@@ -142,12 +353,20 @@ pub fn write_swcrc(is_spring: bool) -> Result<(), Box<dyn Error>> {
             "@/dtos/*": ["dtos/*"],
             "@/interfaces/*": ["interfaces/*"],
             "@/middlewares/*": ["middlewares/*"],
+            "@/shields/*": ["shields/*"],
             "@/models/*": ["models/*"],
             "@/routes/*": ["routes/*"],
             "@/services/*": ["services/*"],
             "@/utils/*": ["utils/*"],
             "@/constants/*": ["constants/*"],
-            "@/validations/*": ["validations/*"]
+            "@/validations/*": ["validations/*"],
+            "@/decorators/*": ["decorators/*"],
+            "@/helpers/*": ["helpers/*"],
+            "@/constants": ["constants"],
+            "@/decorators": ["decorators"],
+            "@/helpers": ["helpers"],
+            "@/interfaces": ["interfaces"],
+            "@/validations": ["validations"]
         })
     } else {
         json!({
@@ -157,13 +376,18 @@ pub fn write_swcrc(is_spring: bool) -> Result<(), Box<dyn Error>> {
             "@/utils/*": ["shared/utils/*"],
             "@/shields/*": ["shared/shields/*"],
             "@/shared/*": ["shared/*"],
-            "@/helpers*": ["shared/helpers/*"],
+            "@/helpers/*": ["shared/helpers/*"],
             "@/interfaces/*": ["shared/interfaces/*"],
             "@/middlewares/*": ["shared/middlewares/*"],
             "@/decorators/*": ["shared/decorators/*"],
             "@/services/*": ["shared/services/*"],
             "@/constants/*": ["shared/constants/*"],
-            "@/validations/*": ["shared/validations/*"]
+            "@/validations/*": ["shared/validations/*"],
+            "@/constants": ["shared/constants"],
+            "@/decorators": ["shared/decorators"],
+            "@/helpers": ["shared/helpers"],
+            "@/interfaces": ["shared/interfaces"],
+            "@/validations": ["shared/validations"]
         })
     };
 
@@ -195,17 +419,13 @@ pub fn write_swcrc(is_spring: bool) -> Result<(), Box<dyn Error>> {
         }
     });
 
-    // Pretty print the JSON with proper indentation
-    let config_str = serde_json::to_string_pretty(&config)?;
-
-    fs::write(file_path, config_str)?;
-
-    Ok(())
+    write_merged_json(project, &file_path, &config, &[], merge)
 }
 
-pub fn write_tsconfig(is_spring: bool) -> Result<(), Box<dyn Error>> {
+pub fn write_tsconfig(project: &Project, merge: MergePolicy) -> Result<()> {
+    let is_spring = project.config().routing == Routing::Spring;
     // Implementation for writing tsconfig.json
-    let root_dir = get_root_directory()?;
+    let root_dir = project.root();
     let file_path = root_dir.join("tsconfig.json");
 
     let paths = if !is_spring {
@@ -216,11 +436,20 @@ pub fn write_tsconfig(is_spring: bool) -> Result<(), Box<dyn Error>> {
           "@/dtos/*": ["dtos/*"],
           "@/interfaces/*": ["interfaces/*"],
           "@/middlewares/*": ["middlewares/*"],
+          "@/shields/*": ["shields/*"],
           "@/models/*": ["models/*"],
           "@/routes/*": ["routes/*"],
           "@/services/*": ["services/*"],
           "@/utils/*": ["utils/*"],
           "@/constants/*": ["constants/*"],
+          "@/validations/*": ["validations/*"],
+          "@/decorators/*": ["decorators/*"],
+          "@/helpers/*": ["helpers/*"],
+          "@/constants": ["constants"],
+          "@/decorators": ["decorators"],
+          "@/helpers": ["helpers"],
+          "@/interfaces": ["interfaces"],
+          "@/validations": ["validations"]
         })
     } else {
         json!({
@@ -230,13 +459,18 @@ pub fn write_tsconfig(is_spring: bool) -> Result<(), Box<dyn Error>> {
           "@/utils/*": ["shared/utils/*"],
           "@/shields/*": ["shared/shields/*"],
           "@/shared/*": ["shared/*"],
-          "@/helpers*": ["shared/helpers/*"],
+          "@/helpers/*": ["shared/helpers/*"],
           "@/interfaces/*": ["shared/interfaces/*"],
           "@/middlewares/*": ["shared/middlewares/*"],
           "@/decorators/*": ["shared/decorators/*"],
           "@/services/*": ["shared/services/*"],
           "@/constants/*": ["shared/constants/*"],
-          "@/validations/*": ["shared/validations/*"]
+          "@/validations/*": ["shared/validations/*"],
+          "@/constants": ["shared/constants"],
+          "@/decorators": ["shared/decorators"],
+          "@/helpers": ["shared/helpers"],
+          "@/interfaces": ["shared/interfaces"],
+          "@/validations": ["shared/validations"]
         })
     };
 
@@ -268,160 +502,124 @@ pub fn write_tsconfig(is_spring: bool) -> Result<(), Box<dyn Error>> {
       }
     });
 
-    // Pretty print the JSON with proper indentation
-    let config_str = serde_json::to_string_pretty(&config)?;
-    fs::write(file_path, config_str)?;
-
-    Ok(())
+    write_merged_json(project, &file_path, &config, &[], merge)
 }
 
-pub fn write_dolph_config() -> Result<(), Box<dyn Error>> {
+pub fn write_dolph_config(project: &Project) -> Result<()> {
     // Implementation for writing dolph config
-    let root_dir = get_root_directory()?;
+    let root_dir = project.root();
     let file_path = root_dir.join("dolph_config.yaml");
 
     let config = DefaultDolphConfig {
-        jsonLimit: String::from("2mb"),
+        json_limit: String::from("2mb"),
         port: String::from("3300"),
     };
 
     // Pretty print the JSON with proper indentation
     let config_str = serde_yaml::to_string(&config)?;
-    fs::write(file_path, config_str)?;
-    Ok(())
-}
-
-pub fn write_package_json(
-    project_name: &str,
-    language: &str,
-    api: &str,
-) -> Result<(), Box<dyn Error>> {
-    // Implementation for writing package.json
-    let root_dir = get_root_directory()?;
-    let file_path = root_dir.join("package.json");
-
-    let config = if language.to_string() == "ts" && api.to_string() == "rest" {
-        json!({
-          "name": project_name.to_string(),
-          "version": "1.0.0",
-          "main": "app/server.js",
-          "author": "",
-          "license": "MIT",
-          "engines": {
-            "node": ">=18.0.0"
-          },
-          "scripts": {
-            "dev:start": "dolph watch",
-            "dev:docker:start": "docker-compose -f docker-compose-dev.yml up",
-            "dev:docker:stop": "docker-compose -f docker-compose-dev.yml down",
-            "build": "dolph build",
-            "build:tsc": "tsc && tsc-alias",
-            "start": "dolph start",
-            "clean": "rm -r app && rm -r logs"
-          },
-          "dependencies": {
-            "@dolphjs/dolph": "^2.0.1"
-          },
-          "devDependencies": {
-            "@swc/cli": "^0.1.62",
-            "@swc/core": "^1.3.91",
-             "@types/express": "^4.17.21",
-            "@types/node": "^20.8.2",
-            "ts-node": "^10.9.1",
-            "tsc-alias": "^1.8.8",
-            "tsconfig-paths": "^4.2.0",
-            "typescript": "^5.2.2"
-          }
-        })
-    } else if api.to_string() == "graphql" {
-        json!({
-         "name": project_name.to_string(),
-          "version": "1.0.0",
-          "main": "app/server.js",
-          "author": "",
-          "license": "MIT",
-          "engines": {
-            "node": ">=18.0.0"
-          },
-          "scripts": {
-            "dev:start": "dolph watch",
-            "dev:docker:start": "docker-compose -f docker-compose-dev.yml up",
-            "dev:docker:stop": "docker-compose -f docker-compose-dev.yml down",
-            "build": "dolph build",
-            "build:tsc": "tsc && tsc-alias",
-            "start": "dolph start",
-            "clean": "rm -r app && rm -r logs"
-          },
-          "dependencies": {
-            "@dolphjs/dolph": "^2.0.1",
-            "@dolphjs/graphql": "^0.2.0",
-            "graphql-scalars": "^1.23.0",
-            "type-graphql": "^2.0.0-rc.2",
-            "typeorm": "^0.3.20"
-          },
-          "devDependencies": {
-            "@swc/cli": "^0.1.62",
-            "@swc/core": "^1.3.91",
-             "@types/express": "^4.17.21",
-            "@types/node": "^20.8.2",
-            "ts-node": "^10.9.1",
-            "tsc-alias": "^1.8.8",
-            "tsconfig-paths": "^4.2.0",
-            "typescript": "^5.2.2"
-          }
-        })
-    } else {
-        json!({
-          "name": project_name.to_string(),
-          "version": "1.0.0",
-          "main": "src/server.js",
-          "author": "",
-          "license": "MIT",
-          "engines": {
-            "node": ">=18.0.0"
-          },
-          "scripts": {
-            "dev:start": "dolph watch",
-            "dev:docker:start": "docker-compose -f docker-compose-dev.yml up",
-            "dev:docker:stop": "docker-compose -f docker-compose-dev.yml down",
-            "clean": "rm -r logs",
-            "start": "dolph start"
-          },
-          "dependencies": {
-            "@dolphjs/dolph": "^2.0.1"
-          }
-        })
-    };
-
-    // Pretty print the JSON with proper indentation
-    let config_str = serde_json::to_string_pretty(&config)?;
-    fs::write(file_path, config_str)?;
+    write_file(project.changes(), &file_path, config_str)?;
     Ok(())
 }
 
-pub fn write_gitignore() -> Result<(), Box<dyn Error>> {
-    // Implementation for writing .gitignore
-    let root_dir = get_root_directory()?;
-    let file_path = root_dir.join(".gitignore");
+pub fn write_gitignore(project: &Project) -> Result<()> {
+    let file_path = project.root().join(".gitignore");
 
-    let config = r#"node_modules
+    let ignored = r#"node_modules
 .env
 logs
 yarn-error.log
-app"#
-        .to_string();
+app
+"#;
+
+    // Rules already in the file are kept, the missing ones are appended
+    let current = read_existing(&file_path)?.unwrap_or_default();
+    let merged = merge_lines(&current, ignored);
+    if merged == current {
+        return Ok(());
+    }
+    write_file(project.changes(), &file_path, merged)
+}
+
+/// Writes `generated` as pretty JSON to `path`. An existing file is deep-merged with it: missing
+/// keys are added, and values the user changed are shown as a diff and only replaced when `merge`
+/// allows it. Values below the top-level keys in `keep`, such as dependency versions, are always
+/// kept.
+pub(super) fn write_merged_json(
+    project: &Project,
+    path: &Path,
+    generated: &serde_json::Value,
+    keep: &[&str],
+    merge: MergePolicy,
+) -> Result<()> {
+    let Some(current) = read_existing(path)? else {
+        // Pretty print the JSON with proper indentation
+        return write_file(
+            project.changes(),
+            path,
+            serde_json::to_string_pretty(generated)?,
+        );
+    };
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    let Ok(existing) = serde_json::from_str::<serde_json::Value>(&current) else {
+        if merge.confirm(&format!(
+            "{} is not valid JSON, replace it with the generated one?",
+            file_name
+        ))? {
+            return write_file(
+                project.changes(),
+                path,
+                serde_json::to_string_pretty(generated)?,
+            );
+        }
+        message(format!(
+            "Kept {}, merge the generated settings by hand",
+            file_name
+        ));
+        return Ok(());
+    };
 
-    let file_content = format!("{}", config);
+    let mut merged = existing.clone();
+    let conflicts: Vec<Conflict> = merge_json(&mut merged, generated)
+        .into_iter()
+        .filter(|conflict| {
+            !conflict
+                .keys
+                .first()
+                .is_some_and(|key| keep.contains(&key.as_str()))
+        })
+        .collect();
 
-    match fs::write(&file_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing server file: {}", e));
-            Err(Box::new(e))
+    if !conflicts.is_empty() {
+        message(format!("{} has values dolph would change:", file_name));
+        for conflict in &conflicts {
+            message(format!("- {}: {}", conflict.key(), conflict.current));
+            message(format!("+ {}: {}", conflict.key(), conflict.generated));
+        }
+        if merge.confirm("Replace them with the generated values?")? {
+            for conflict in &conflicts {
+                conflict.resolve(&mut merged);
+            }
+        } else {
+            message(format!("Kept your values in {}", file_name));
         }
     }
+
+    if merged == existing {
+        return Ok(());
+    }
+    write_file(
+        project.changes(),
+        path,
+        serde_json::to_string_pretty(&merged)?,
+    )
 }
 
-fn get_root_directory() -> Result<PathBuf, Box<dyn Error>> {
-    Ok(std::env::current_dir()?)
+/// The content of `path`, or `None` when it does not exist.
+fn read_existing(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(DolphError::write(path, e)),
+    }
 }
@@ -1,37 +1,42 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_spring_controller(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_spring_controller(project: &Project, name: &ResourceName, crud: bool) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let controller_path = component_path.join(format!("{}", name));
+    let controller_path = name.dir(&component_path);
 
     ensure_directory_exists(&controller_path)?;
 
-    let index_path = controller_path.join(format!("{}.controller.ts", name));
+    let index_path = controller_path.join(format!("{}.controller.ts", name.kebab()));
 
-    let capitalized_name = capitalize_first_letter(name);
+    let capitalized_name = name.pascal();
+    let camel_name = name.camel();
+    let route_path = name.route_path(project.config().naming);
 
-    let import_statement = format!(
-        r#"import {{ DolphControllerHandler }} from "@dolphjs/dolph/classes";
-import {{
+    let (import_statement, other_body) = if crud {
+        (
+            crud_import_statement(name, &capitalized_name),
+            crud_body(name, &capitalized_name, &route_path),
+        )
+    } else {
+        (
+            r#"import { DolphControllerHandler } from "@dolphjs/dolph/classes";
+import {
   Dolph,
   SuccessResponse,
   DRequest,
   DResponse
-}} from "@dolphjs/dolph/common";
-import {{ Get, Route }} from "@dolphjs/dolph/decorators";"#
-    );
-
-    let other_body = format!(
-        r#"@Route('{name}')
+} from "@dolphjs/dolph/common";
+import { Get, Route } from "@dolphjs/dolph/decorators";"#
+                .to_string(),
+            format!(
+                r#"@Route('{route_path}')
 export class {capitalized_name}Controller extends DolphControllerHandler<Dolph> {{
   constructor() {{
     super();
@@ -39,18 +44,94 @@ export class {capitalized_name}Controller extends DolphControllerHandler<Dolph>
 
   @Get("greet")
   async greet (req: DRequest, res: DResponse) {{
-    SuccessResponse({{ res, body: {{ message: "you've reached the {name} endpoint." }} }});
+    SuccessResponse({{ res, body: {{ message: "you've reached the {camel_name} endpoint." }} }});
     }};
 }}"#
-    );
+            ),
+        )
+    };
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_body);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing controller file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn crud_import_statement(name: &ResourceName, capitalized_name: &str) -> String {
+    let file_name = name.kebab();
+
+    format!(
+        r#"import {{ DolphControllerHandler }} from "@dolphjs/dolph/classes";
+import {{
+  Dolph,
+  SuccessResponse,
+  NotFoundException,
+  DRequest,
+  DResponse
+}} from "@dolphjs/dolph/common";
+import {{ Delete, Get, Patch, Post, Put, Route }} from "@dolphjs/dolph/decorators";
+import {{ {capitalized_name}Service }} from "./{file_name}.service";
+import {{ Create{capitalized_name}Dto, Update{capitalized_name}Dto }} from "./{file_name}.dto";"#
+    )
+}
+
+fn crud_body(name: &ResourceName, capitalized_name: &str, route_path: &str) -> String {
+    let camel_name = name.camel();
+
+    format!(
+        r#"@Route('{route_path}')
+export class {capitalized_name}Controller extends DolphControllerHandler<Dolph> {{
+  private {camel_name}Service: {capitalized_name}Service;
+
+  constructor() {{
+    super();
+  }}
+
+  @Post()
+  async create(req: DRequest, res: DResponse) {{
+    const data = await this.{camel_name}Service.create(req.body as Create{capitalized_name}Dto);
+    SuccessResponse({{ res, status: 201, body: data }});
+  }}
+
+  @Get()
+  async findAll(req: DRequest, res: DResponse) {{
+    const page = Math.max(Number(req.query.page) || 1, 1);
+    const limit = Math.min(Math.max(Number(req.query.limit) || 10, 1), 100);
+
+    const data = await this.{camel_name}Service.findAll(page, limit);
+    SuccessResponse({{ res, body: data }});
+  }}
+
+  @Get(":id")
+  async findOne(req: DRequest, res: DResponse) {{
+    const data = await this.{camel_name}Service.findById(req.params.id);
+    if (!data) throw new NotFoundException("{camel_name} not found");
+
+    SuccessResponse({{ res, body: data }});
+  }}
+
+  @Put(":id")
+  async replace(req: DRequest, res: DResponse) {{
+    const data = await this.{camel_name}Service.update(req.params.id, req.body as Create{capitalized_name}Dto);
+    if (!data) throw new NotFoundException("{camel_name} not found");
+
+    SuccessResponse({{ res, body: data }});
+  }}
+
+  @Patch(":id")
+  async update(req: DRequest, res: DResponse) {{
+    const data = await this.{camel_name}Service.update(req.params.id, req.body as Update{capitalized_name}Dto);
+    if (!data) throw new NotFoundException("{camel_name} not found");
+
+    SuccessResponse({{ res, body: data }});
+  }}
+
+  @Delete(":id")
+  async delete(req: DRequest, res: DResponse) {{
+    const data = await this.{camel_name}Service.delete(req.params.id);
+    if (!data) throw new NotFoundException("{camel_name} not found");
+
+    SuccessResponse({{ res, body: data }});
+  }}
+}}"#
+    )
 }
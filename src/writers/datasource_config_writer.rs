@@ -1,11 +1,97 @@
-use std::{error::Error, fs};
+use std::fs;
+use std::path::PathBuf;
 
-use crate::utils::ensure_directory_exists;
+use crate::config::Database;
+use crate::error::{DolphError, Result};
+use crate::output::message;
+use crate::project::Project;
+use crate::utils::{
+    add_import, add_to_array, ensure_directory_exists, replace_generated, write_file, ResourceName,
+};
 
-use super::config_writers::{find_base_directory, Database};
+use super::env_writer::write_env_config;
 
-pub fn write_datasource_config(mut database: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_datasource_config(project: &Project) -> Result<()> {
+    let index_path = datasource_path(project)?;
+
+    write_env_config(project)?;
+
+    write_file(
+        project.changes(),
+        &index_path,
+        datasource_content(project.config().database),
+    )
+}
+
+/// Rewrites `data_source.ts` for the configured database, unless it was changed since it was
+/// generated for `previous`. Returns whether the file is up to date.
+pub fn update_datasource_config(project: &Project, previous: Database) -> Result<bool> {
+    let path = datasource_path(project)?;
+    // Entities registered since are carried over, they are part of the generated file
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    let entities = registered_entities(&current);
+
+    replace_generated(
+        project.changes(),
+        &path,
+        &with_entities(datasource_content(previous), &entities),
+        &with_entities(datasource_content(project.config().database), &entities),
+    )
+}
+
+/// Lists the entity generated for `name` in the `entities` of `data_source.ts`, which typeorm only
+/// hands out repositories for.
+pub fn register_entity(project: &Project, name: &ResourceName) -> Result<()> {
+    let path = datasource_path(project)?;
+    if !path.exists() {
+        write_datasource_config(project)?;
+    }
+    let content = fs::read_to_string(&path).map_err(|e| DolphError::write(&path, e))?;
+
+    let entity = name.pascal();
+    let Some(content) = add_to_array(&content, "entities: [", &entity) else {
+        message(format!(
+            "Could not find `entities: [...]` in data_source.ts, register {} there manually",
+            entity
+        ));
+        return Ok(());
+    };
+
+    let module = format!(
+        "../../components/{}/entities/{}.entity",
+        name.path(),
+        name.kebab()
+    );
+    write_file(
+        project.changes(),
+        &path,
+        add_import(&content, &entity, &module),
+    )
+}
+
+/// The entities imported into `content` by [`register_entity`], with the module of each.
+fn registered_entities(content: &str) -> Vec<(&str, &str)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (entity, module) = line.strip_prefix("import { ")?.split_once(" } from \"")?;
+            let module = module.strip_suffix("\";")?;
+            module.ends_with(".entity").then_some((entity, module))
+        })
+        .collect()
+}
+
+fn with_entities(mut content: String, entities: &[(&str, &str)]) -> String {
+    for (entity, module) in entities {
+        if let Some(registered) = add_to_array(&content, "entities: [", entity) {
+            content = add_import(&registered, entity, module);
+        }
+    }
+    content
+}
+
+fn datasource_path(project: &Project) -> Result<PathBuf> {
+    let base_directory = project.src_dir()?;
 
     let shared_path = base_directory.join("shared");
 
@@ -15,45 +101,44 @@ pub fn write_datasource_config(mut database: &str) -> Result<(), Box<dyn Error>>
 
     ensure_directory_exists(&configs_path)?;
 
-    let index_path = configs_path.join("data_source.ts");
+    Ok(configs_path.join("data_source.ts"))
+}
 
+fn datasource_content(database: Database) -> String {
     let import_statement = r#"import { DataSource } from "typeorm";
-    "#
-    .to_string();
+import { env } from "./env.config";"#
+        .to_string();
 
-    let mut db = database;
+    // typeorm driver names differ from the ones used in dolph_cli.yaml, `type` has to be one of
+    // them for the data source to compile, so other databases start out on postgres
+    let db = match database {
+        Database::MySQL => "mysql",
+        Database::MongoDB => "mongodb",
+        Database::PostgreSQL | Database::Other => "postgres",
+    };
 
-    match &mut database {
-        &mut "mysql" => db = "mysql",
-        &mut "mongo" => db = "mongo",
-        &mut "postgresql" => db = "postgres",
-        &mut "other" => db = "",
-        &mut &_ => (),
-    }
+    // The connection settings come from `.env` through env.config.ts
+    let connection = if database == Database::MongoDB {
+        "  url: env.database.url,"
+    } else {
+        r#"  host: env.database.host,
+  port: env.database.port,
+  username: env.database.username,
+  password: env.database.password,
+  database: env.database.name,"#
+    };
 
     let file_content = format!(
         r#"export const AppDataSource = new DataSource({{
-   type: "{db}",
-   host: "localhost",
-   port: 5473,
-   username: "user",
-   password: "password123",
-   database: "dolph-app",
-   entities: [],
-   synchronize: process.env.NODE_ENV === "development",
-   logging: true,
-   migrations: [__dirname + "/migration/*.ts"], 
+  type: "{db}",
+{connection}
+  entities: [],
+  synchronize: env.nodeEnv === "development",
+  logging: true,
+  migrations: [__dirname + "/migration/*.ts"],
 }});
 "#
     );
 
-    let file_content = format!("{}\n\n{}\n", import_statement, file_content);
-
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing datasource config file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    format!("{}\n\n{}\n", import_statement, file_content)
 }
@@ -1,11 +1,11 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file};
 
-use crate::utils::ensure_directory_exists;
+use super::env_writer::write_env_config;
 
-use super::config_writers::find_base_directory;
-
-pub fn write_db_config(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_db_config(project: &Project) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let shared_path = base_directory.join("shared");
 
@@ -17,25 +17,21 @@ pub fn write_db_config(name: &str) -> Result<(), Box<dyn Error>> {
 
     let index_path = configs_path.join("db.config.ts");
 
+    write_env_config(project)?;
+
     let import_statement = r#"import { initMySql } from "@dolphjs/dolph/packages";
-import {} from "..""#
+import { env } from "./env.config";"#
         .to_string();
 
     let file_content = r#"export const sequelizeInstance = initMySql(
-  "dolph",
-  "root",
-  "password",
-  "localhost"
+  env.database.name,
+  env.database.username,
+  env.database.password,
+  env.database.host
 );"#
     .to_string();
 
     let file_content = format!("{}\n\n{}\n", import_statement, file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing db config file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
 }
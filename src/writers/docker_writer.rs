@@ -0,0 +1,168 @@
+use std::fs;
+
+use crate::config::{Database, Language};
+use crate::error::Result;
+use crate::output::message;
+use crate::project::Project;
+use crate::utils::write_file;
+
+use super::env_writer::database_name;
+use super::MergePolicy;
+
+/// Writes a multi-stage `Dockerfile`, `.dockerignore` and the `docker-compose-dev.yml` the
+/// `dev:docker:start` script runs, with a service for the configured database. Existing files
+/// that differ are only replaced when `merge` agrees.
+pub fn write_docker_files(project: &Project, project_name: &str, merge: MergePolicy) -> Result<()> {
+    let config = project.config();
+
+    // The `dev` stage runs `dolph watch`, the production image only gets the compiled app
+    let entry = config.language.entry_file();
+    let production_stage = if config.language == Language::Ts {
+        format!(
+            r#"FROM dev AS build
+RUN dolph build
+
+FROM node:20-alpine AS production
+WORKDIR /usr/src/app
+ENV NODE_ENV=production
+COPY package*.json ./
+RUN npm install --omit=dev
+COPY dolph_config.yaml ./
+COPY --from=build /usr/src/app/app ./app
+EXPOSE 3300
+CMD ["node", "{entry}"]"#
+        )
+    } else {
+        format!(
+            r#"FROM node:20-alpine AS production
+WORKDIR /usr/src/app
+ENV NODE_ENV=production
+COPY package*.json ./
+RUN npm install --omit=dev
+COPY dolph_config.yaml ./
+COPY src ./src
+EXPOSE 3300
+CMD ["node", "{entry}"]"#
+        )
+    };
+
+    let dockerfile = format!(
+        r#"FROM node:20-alpine AS dev
+WORKDIR /usr/src/app
+RUN npm install -g @dolphjs/cli
+COPY package*.json ./
+RUN npm install
+COPY . .
+EXPOSE 3300
+CMD ["npm", "run", "dev:start"]
+
+{production_stage}
+"#
+    );
+    write_docker_file(project, merge, "Dockerfile", &dockerfile)?;
+
+    let dockerignore = r#"node_modules
+app
+logs
+.env
+.git
+Dockerfile
+docker-compose-dev.yml
+"#;
+    write_docker_file(project, merge, ".dockerignore", dockerignore)?;
+
+    // Credentials are substituted from .env, which docker compose reads on its own. Other
+    // databases get no service, the app connects to the one set up in .env
+    let database = match config.database {
+        Database::MongoDB => Some((
+            format!(
+                "MONGO_URL: mongodb://db:27017/{}",
+                database_name(project_name)
+            ),
+            r#"    image: mongo:7
+    ports:
+      - "27017:27017"
+    volumes:
+      - db-data:/data/db"#,
+        )),
+        Database::MySQL => Some((
+            String::from("DB_HOST: db"),
+            r#"    image: mysql:8.0
+    environment:
+      MYSQL_DATABASE: ${DB_NAME}
+      MYSQL_USER: ${DB_USERNAME}
+      MYSQL_PASSWORD: ${DB_PASSWORD}
+      MYSQL_ROOT_PASSWORD: ${DB_PASSWORD}
+    ports:
+      - "${DB_PORT}:3306"
+    volumes:
+      - db-data:/var/lib/mysql"#,
+        )),
+        Database::PostgreSQL => Some((
+            String::from("DB_HOST: db"),
+            r#"    image: postgres:16-alpine
+    environment:
+      POSTGRES_DB: ${DB_NAME}
+      POSTGRES_USER: ${DB_USERNAME}
+      POSTGRES_PASSWORD: ${DB_PASSWORD}
+    ports:
+      - "${DB_PORT}:5432"
+    volumes:
+      - db-data:/var/lib/postgresql/data"#,
+        )),
+        Database::Other => None,
+    };
+
+    let app = r#"services:
+  app:
+    build:
+      context: .
+      target: dev
+    env_file: .env"#;
+    let app_ports = r#"    ports:
+      - "3300:3300"
+    volumes:
+      - ./src:/usr/src/app/src"#;
+
+    let compose = match database {
+        Some((app_environment, database_service)) => format!(
+            r#"{app}
+    environment:
+      {app_environment}
+{app_ports}
+    depends_on:
+      - db
+  db:
+{database_service}
+
+volumes:
+  db-data:
+"#
+        ),
+        None => format!("{app}\n{app_ports}\n"),
+    };
+    write_docker_file(project, merge, "docker-compose-dev.yml", &compose)
+}
+
+fn write_docker_file(
+    project: &Project,
+    merge: MergePolicy,
+    file_name: &str,
+    content: &str,
+) -> Result<()> {
+    let path = project.root().join(file_name);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if existing == content {
+            return Ok(());
+        }
+        if !merge.confirm(&format!(
+            "{} exists, replace it with the generated one?",
+            file_name
+        ))? {
+            message(format!("Kept {}", file_name));
+            return Ok(());
+        }
+    }
+
+    write_file(project.changes(), &path, content)
+}
@@ -1,38 +1,104 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{
+    ensure_directory_exists, validator_imports, write_file, Field, FieldType, ResourceName,
+};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_spring_dto(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_spring_dto(
+    project: &Project,
+    name: &ResourceName,
+    fields: &[Field],
+    crud: bool,
+) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let dto_path = component_path.join(format!("{}", name));
+    let dto_path = name.dir(&component_path);
 
     ensure_directory_exists(&dto_path)?;
 
-    let index_path = dto_path.join(format!("{}.dto.ts", name));
+    let index_path = dto_path.join(format!("{}.dto.ts", name.kebab()));
 
-    let capitalized_name = capitalize_first_letter(name);
+    let capitalized_name = name.pascal();
+
+    if !fields.is_empty() {
+        return write_file(
+            project.changes(),
+            &index_path,
+            fields_file_content(&capitalized_name, fields, crud),
+        );
+    }
 
     let import_statement = r#"import {} from 'class-validator';
 import {} from 'class-transformer';
     "#
     .to_string();
 
-    let other_content = format!(r#"export class Create{capitalized_name}Dto {{}}"#);
+    let other_content = if crud {
+        format!(
+            r#"export class Create{capitalized_name}Dto {{}}
+
+export class Update{capitalized_name}Dto implements Partial<Create{capitalized_name}Dto> {{}}"#
+        )
+    } else {
+        format!(r#"export class Create{capitalized_name}Dto {{}}"#)
+    };
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing service file: {}", e));
-            Err(Box::new(e))
-        }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn fields_file_content(capitalized_name: &str, fields: &[Field], crud: bool) -> String {
+    let extra: &[&str] = if crud { &["IsOptional"] } else { &[] };
+    let mut imports = vec![format!(
+        "import {{ {} }} from 'class-validator';",
+        validator_imports(fields, extra).join(", ")
+    )];
+    if fields.iter().any(|field| field.kind == FieldType::Date) {
+        imports.push(String::from("import { Type } from 'class-transformer';"));
     }
+
+    let mut content = format!(
+        "{}\n\nexport class Create{capitalized_name}Dto {{\n{}}}\n",
+        imports.join("\n"),
+        properties(fields, false)
+    );
+
+    if crud {
+        content.push_str(&format!(
+            "\nexport class Update{capitalized_name}Dto implements Partial<Create{capitalized_name}Dto> {{\n{}}}\n",
+            properties(fields, true)
+        ));
+    }
+
+    content
+}
+
+fn properties(fields: &[Field], optional: bool) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let mut decorators = Vec::new();
+            if optional {
+                decorators.push(String::from("  @IsOptional()"));
+            }
+            if field.kind == FieldType::Date {
+                decorators.push(String::from("  @Type(() => Date)"));
+            }
+            decorators.push(format!("  @{}()", field.kind.validator()));
+
+            format!(
+                "{}\n  {}{}: {};\n",
+                decorators.join("\n"),
+                field.name,
+                if optional { "?" } else { "" },
+                field.kind.ts_type()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -1,18 +1,21 @@
-use std::{error::Error, fs};
+use crate::config::Database;
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, Field, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_entity(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory =
-        find_base_directory().ok_or_else(|| "Could not find the base directory")?;
+pub fn write_entity(
+    project: &Project,
+    name: &ResourceName,
+    fields: &[Field],
+    crud: bool,
+) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let name_path = component_path.join(format!("{}", name));
+    let name_path = name.dir(&component_path);
 
     ensure_directory_exists(&name_path)?;
 
@@ -20,33 +23,107 @@ pub fn write_entity(name: &str) -> Result<(), Box<dyn Error>> {
 
     ensure_directory_exists(&entities_path)?;
 
-    let index_path = entities_path.join(format!("{}.entity.ts", name));
+    let index_path = entities_path.join(format!("{}.entity.ts", name.kebab()));
 
-    let capitalized_name = capitalize_first_letter(name);
+    let capitalized_name = name.pascal();
+    let index_name = name.snake();
+    let table_name = name.table_name(project.config().naming);
 
-    let import_statement = r#"import { Field, ID, ObjectType } from "type-graphql";
-import { PrimaryGeneratedColumn } from "typeorm";"#
-        .to_string();
+    let mongo = project.config().database == Database::MongoDB;
 
-    let other_file_content = format!(
-        r#"@ObjectType()
-@Entity()
-@Index("{name}_index_0", ["id"])
+    let typeorm_imports = match (mongo, fields.is_empty()) {
+        (true, true) => "Entity, ObjectIdColumn",
+        (true, false) => "Column, Entity, ObjectIdColumn",
+        (false, true) => "Entity, Index, PrimaryGeneratedColumn",
+        (false, false) => "Column, Entity, Index, PrimaryGeneratedColumn",
+    };
+    let object_id_import = if mongo {
+        "\nimport { ObjectId } from \"mongodb\";"
+    } else {
+        ""
+    };
+
+    let import_statement = format!(
+        r#"import {{ Field, ID, ObjectType }} from "type-graphql";
+import {{ {typeorm_imports} }} from "typeorm";{object_id_import}"#
+    );
+
+    let columns: String = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "\n    @Field(() => {})\n    @Column()\n    {}: {};\n",
+                field.kind.graphql_type(),
+                field.name,
+                field.kind.ts_type()
+            )
+        })
+        .collect();
+
+    // MongoDB documents are keyed by the ObjectId in `_id`, which is indexed already
+    let other_file_content = if mongo {
+        format!(
+            r#"@ObjectType()
+@Entity("{table_name}")
+export class {capitalized_name} {{
+    @Field(() => ID)
+    @ObjectIdColumn()
+    _id: ObjectId;
+{columns}}}
+"#
+        )
+    } else {
+        format!(
+            r#"@ObjectType()
+@Entity("{table_name}")
+@Index("{index_name}_index_0", ["id"])
 export class {capitalized_name} {{
     @Field(() => ID)
     @PrimaryGeneratedColumn("uuid")
     id: string;
-}}
+{columns}}}
 "#
-    );
+        )
+    };
+
+    let file_content = if crud {
+        format!(
+            "{}\n\n{}\n{}\n",
+            crud_import_statement(typeorm_imports, object_id_import),
+            other_file_content,
+            paginated_type(&capitalized_name)
+        )
+    } else {
+        format!("{}\n\n{}\n", import_statement, other_file_content)
+    };
+
+    write_file(project.changes(), &index_path, file_content)
+}
 
-    let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
+fn crud_import_statement(typeorm_imports: &str, object_id_import: &str) -> String {
+    format!(
+        r#"import {{ Field, ID, Int, ObjectType }} from "type-graphql";
+import {{ {typeorm_imports} }} from "typeorm";{object_id_import}"#
+    )
+}
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing entity file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+/// The object type returned by the paginated list query.
+fn paginated_type(capitalized_name: &str) -> String {
+    format!(
+        r#"@ObjectType()
+export class Paginated{capitalized_name} {{
+    @Field(() => [{capitalized_name}])
+    items: {capitalized_name}[];
+
+    @Field(() => Int)
+    total: number;
+
+    @Field(() => Int)
+    page: number;
+
+    @Field(() => Int)
+    limit: number;
+}}
+"#
+    )
 }
@@ -0,0 +1,218 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{ConfigValue, Database, Language};
+use crate::error::{DolphError, Result};
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, replace_generated, write_file};
+
+/// Variables left empty in `.env.example`.
+const SECRETS: &[&str] = &["DB_PASSWORD"];
+
+/// Written above the connection variables of databases dolph has no driver for.
+const PLACEHOLDER_NOTE: &str = "# Placeholders, fill in the connection settings of your database\n";
+
+/// The variables of `.env` with their development values.
+fn env_vars(database: Database, database_name: &str) -> Vec<(&'static str, String)> {
+    let mut vars = vec![
+        ("NODE_ENV", String::from("development")),
+        ("PORT", String::from("3300")),
+    ];
+
+    let port = match database {
+        Database::MongoDB => {
+            let url = format!("mongodb://localhost:27017/{}", database_name);
+            vars.push(("MONGO_URL", url));
+            return vars;
+        }
+        Database::MySQL => "3306",
+        Database::PostgreSQL => "5432",
+        Database::Other => {
+            vars.extend(
+                [
+                    "DB_HOST",
+                    "DB_PORT",
+                    "DB_USERNAME",
+                    "DB_PASSWORD",
+                    "DB_NAME",
+                ]
+                .map(|name| (name, String::new())),
+            );
+            return vars;
+        }
+    };
+
+    vars.extend([
+        ("DB_HOST", String::from("localhost")),
+        ("DB_PORT", port.to_string()),
+        ("DB_USERNAME", String::from("dolph")),
+        ("DB_PASSWORD", String::from("dolph")),
+        ("DB_NAME", database_name.to_string()),
+    ]);
+    vars
+}
+
+/// The database name for `project_name`, which may be a scoped npm package name.
+pub(super) fn database_name(project_name: &str) -> String {
+    project_name
+        .rsplit('/')
+        .next()
+        .unwrap_or(project_name)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Renders `vars` as lines of a `.env` file, with the connection variables of databases dolph has
+/// no driver for marked as placeholders.
+fn render_env<'a>(database: Database, vars: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    vars.map(|(name, value)| {
+        let note = if database == Database::Other && name == "DB_HOST" {
+            PLACEHOLDER_NOTE
+        } else {
+            ""
+        };
+        format!("{}{}={}\n", note, name, value)
+    })
+    .collect()
+}
+
+/// Writes `.env.example`, and `.env` with the development values. An existing `.env` keeps its
+/// values and only gets the variables it is missing.
+pub fn write_env_files(project: &Project, project_name: &str) -> Result<()> {
+    let database = project.config().database;
+    let vars = env_vars(database, &database_name(project_name));
+
+    let example = render_env(
+        database,
+        vars.iter().map(|(name, value)| {
+            let value = if SECRETS.contains(name) { "" } else { value };
+            (*name, value)
+        }),
+    );
+    write_file(
+        project.changes(),
+        &project.root().join(".env.example"),
+        format!("# Copy to .env and fill in the empty values\n{}", example),
+    )?;
+
+    let env_path = project.root().join(".env");
+    let mut env = if env_path.exists() {
+        fs::read_to_string(&env_path).map_err(|e| DolphError::write(&env_path, e))?
+    } else {
+        String::new()
+    };
+    let defined: Vec<&str> = env
+        .lines()
+        .filter_map(|line| line.split_once('=').map(|(name, _)| name.trim()))
+        .collect();
+    let missing = render_env(
+        database,
+        vars.iter()
+            .filter(|(name, _)| !defined.contains(name))
+            .map(|(name, value)| (*name, value.as_str())),
+    );
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if !env.is_empty() && !env.ends_with('\n') {
+        env.push('\n');
+    }
+    env.push_str(&missing);
+    write_file(project.changes(), &env_path, env)
+}
+
+/// Writes `shared/configs/env.config.ts`, or `env.config.js` in javascript projects, the access to
+/// `.env` the other configs import, unless it exists already.
+pub fn write_env_config(project: &Project) -> Result<()> {
+    let index_path = env_config_path(project)?;
+    if index_path.exists() {
+        return Ok(());
+    }
+
+    write_file(
+        project.changes(),
+        &index_path,
+        env_config_content(project.config().database, project.config().language),
+    )
+}
+
+/// Rewrites the env config for the configured database, unless it was changed since it was
+/// generated for `previous`. Returns whether the file is up to date.
+pub fn update_env_config(project: &Project, previous: Database) -> Result<bool> {
+    replace_generated(
+        project.changes(),
+        &env_config_path(project)?,
+        &env_config_content(previous, project.config().language),
+        &env_config_content(project.config().database, project.config().language),
+    )
+}
+
+/// The env config below `src`, `shared/configs/env.config.ts` or `.js`.
+pub fn env_config_file(language: Language) -> String {
+    format!("shared/configs/env.config.{}", language.as_str())
+}
+
+fn env_config_path(project: &Project) -> Result<PathBuf> {
+    let path = project
+        .src_dir()?
+        .join(env_config_file(project.config().language));
+
+    if let Some(configs_path) = path.parent() {
+        ensure_directory_exists(configs_path)?;
+    }
+
+    Ok(path)
+}
+
+fn env_config_content(database: Database, language: Language) -> String {
+    let database = if database == Database::MongoDB {
+        r#"  database: {
+    url: read("MONGO_URL"),
+  },"#
+    } else {
+        r#"  database: {
+    host: read("DB_HOST"),
+    port: Number(read("DB_PORT")),
+    username: read("DB_USERNAME"),
+    password: read("DB_PASSWORD"),
+    name: read("DB_NAME"),
+  },"#
+    };
+
+    // JavaScript projects are CommonJS, only TypeScript ones use module syntax
+    let (load, signature, declaration, end) = match language {
+        Language::Ts => (
+            r#"import "dotenv/config";"#,
+            "read(name: string, fallback?: string): string",
+            "export const env",
+            "} as const;",
+        ),
+        Language::Js => (
+            r#"require("dotenv/config");"#,
+            "read(name, fallback)",
+            "const env",
+            "};\n\nmodule.exports = { env };",
+        ),
+    };
+
+    format!(
+        r#"{load}
+
+function {signature} {{
+  const value = process.env[name] ?? fallback;
+  if (value === undefined) {{
+    throw new Error(`${{name}} is not set, add it to .env (see .env.example)`);
+  }}
+  return value;
+}}
+
+{declaration} = {{
+  nodeEnv: read("NODE_ENV", "development"),
+  port: Number(read("PORT", "3300")),
+{database}
+{end}
+"#
+    )
+}
@@ -1,14 +1,14 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::write_file;
 
-use super::config_writers::find_base_directory;
+use super::config_writers::server_path;
 
-pub fn write_graphql_server_file() -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
-
-    let index_path = base_directory.join("server.ts");
+pub fn write_graphql_server_file(project: &Project) -> Result<()> {
+    let index_path = server_path(project)?;
 
     let import_statement = r#"import { DolphFactory} from "@dolphjs/dolph";
-import { context, schema, session } from "./setup";
+import { context, schema } from "./setup";
 import { AppDataSource } from "./shared/configs/data_source";
 import { logger } from "@dolphjs/dolph/utilities";
 "#
@@ -27,11 +27,5 @@ AppDataSource.initialize()
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing server file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
 }
@@ -1,17 +1,20 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, validator_imports, write_file, Field, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_input(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_input(
+    project: &Project,
+    name: &ResourceName,
+    fields: &[Field],
+    crud: bool,
+) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let name_directory = component_path.join(format!("{}", name));
+    let name_directory = name.dir(&component_path);
 
     ensure_directory_exists(&name_directory)?;
 
@@ -19,14 +22,36 @@ pub fn write_input(name: &str) -> Result<(), Box<dyn Error>> {
 
     ensure_directory_exists(&input_path)?;
 
-    let index_path = input_path.join(format!("{}.input.ts", name));
+    let index_path = input_path.join(format!("{}.input.ts", name.kebab()));
 
-    let capitalized_name = capitalize_first_letter(name);
+    let capitalized_name = name.pascal();
 
-    let import_statement = format!(
-        r#"import {{}} from "class-validator";
-import {{ Field, InputType }} from "type-graphql";"#
-    );
+    if crud {
+        return write_file(
+            project.changes(),
+            &index_path,
+            crud_file_content(&capitalized_name, fields),
+        );
+    }
+
+    if !fields.is_empty() {
+        let file_content = format!(
+            r#"import {{ {validators} }} from "class-validator";
+import {{ Field, InputType }} from "type-graphql";
+
+@InputType()
+export class Create{capitalized_name}Input {{
+{properties}}}
+"#,
+            validators = validator_imports(fields, &[]).join(", "),
+            properties = properties(fields, false),
+        );
+        return write_file(project.changes(), &index_path, file_content);
+    }
+
+    let import_statement = r#"import {} from "class-validator";
+import { Field, InputType } from "type-graphql";"#
+        .to_string();
 
     let other_file_content = format!(
         r#"@InputType()
@@ -36,11 +61,76 @@ export class Create{capitalized_name}Input{{}}
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing input file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn crud_file_content(capitalized_name: &str, fields: &[Field]) -> String {
+    let (validators, create_fields, update_fields) = if fields.is_empty() {
+        (
+            String::from("Max, Min"),
+            format!("    // Add the fields of your {capitalized_name} entity here\n"),
+            String::from(
+                "    // Add the fields that can be updated here, all of them should be nullable\n",
+            ),
+        )
+    } else {
+        (
+            validator_imports(fields, &["IsOptional", "Max", "Min"]).join(", "),
+            properties(fields, false),
+            properties(fields, true),
+        )
+    };
+
+    format!(
+        r#"import {{ {validators} }} from "class-validator";
+import {{ ArgsType, Field, InputType, Int }} from "type-graphql";
+
+@InputType()
+export class Create{capitalized_name}Input {{
+{create_fields}}}
+
+@InputType()
+export class Update{capitalized_name}Input implements Partial<Create{capitalized_name}Input> {{
+{update_fields}}}
+
+@ArgsType()
+export class Paginate{capitalized_name}Args {{
+    @Field(() => Int, {{ defaultValue: 1 }})
+    @Min(1)
+    page: number = 1;
+
+    @Field(() => Int, {{ defaultValue: 10 }})
+    @Min(1)
+    @Max(100)
+    limit: number = 10;
+}}
+"#
+    )
+}
+
+/// The input properties, nullable ones are optional and skip validation when missing.
+fn properties(fields: &[Field], nullable: bool) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if nullable {
+                format!(
+                    "    @Field(() => {}, {{ nullable: true }})\n    @IsOptional()\n    @{}()\n    {}?: {};\n",
+                    field.kind.graphql_type(),
+                    field.kind.validator(),
+                    field.name,
+                    field.kind.ts_type()
+                )
+            } else {
+                format!(
+                    "    @Field(() => {})\n    @{}()\n    {}: {};\n",
+                    field.kind.graphql_type(),
+                    field.kind.validator(),
+                    field.name,
+                    field.kind.ts_type()
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
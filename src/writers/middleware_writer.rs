@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{Language, Routing};
+use crate::error::{DolphError, Result};
+use crate::project::Project;
+use crate::utils::{add_import, ensure_directory_exists, write_file, ResourceName};
+
+/// Writes a middleware, the function dolph runs before the handlers it is applied to.
+pub fn write_middleware(project: &Project, name: &ResourceName) -> Result<()> {
+    let index_path = shared_file(project, "middlewares", name, "middleware")?;
+
+    let camel_name = name.camel();
+
+    let file_content = format!(
+        r#"import {{ DNextFunc, DRequest, DResponse }} from "@dolphjs/dolph/common";
+
+export const {camel_name}Middleware = (req: DRequest, res: DResponse, next: DNextFunc) => {{
+  // Inspect or prepare the request here, then hand it on
+  next();
+}};
+"#
+    );
+
+    write_file(project.changes(), &index_path, file_content)
+}
+
+/// Writes a shield, a middleware that rejects requests before they reach a controller.
+pub fn write_shield(project: &Project, name: &ResourceName) -> Result<()> {
+    let index_path = shared_file(project, "shields", name, "shield")?;
+
+    let camel_name = name.camel();
+
+    let file_content = format!(
+        r#"import {{ DNextFunc, DRequest, DResponse, UnauthorizedException }} from "@dolphjs/dolph/common";
+
+export const {camel_name}Shield = async (req: DRequest, res: DResponse, next: DNextFunc) => {{
+  // Replace with the check the shield guards, e.g. verifying a token
+  if (!req.headers.authorization) {{
+    return next(new UnauthorizedException("{camel_name} shield rejected the request"));
+  }}
+
+  next();
+}};
+"#
+    );
+
+    write_file(project.changes(), &index_path, file_content)
+}
+
+/// The import path of a middleware or shield, through the `@/middlewares/*` and `@/shields/*`
+/// aliases of tsconfig.json. Javascript projects have no aliases, so `@/` mirrors `src`.
+pub fn middleware_import(project: &Project, kind: &str, name: &ResourceName) -> String {
+    let directory = if kind == "shield" {
+        "shields"
+    } else {
+        "middlewares"
+    };
+    let config = project.config();
+    let parent = if config.language == Language::Js && config.routing == Routing::Spring {
+        format!("shared/{}", directory)
+    } else {
+        directory.to_string()
+    };
+
+    format!("@/{}/{}.{}", parent, name.path(), kind)
+}
+
+/// Applies the middleware `export` to the controller of `controller`, with `@Shield` on the class
+/// or `@UseMiddleware` on its `method` handler.
+pub fn apply_to_controller(
+    project: &Project,
+    controller: &ResourceName,
+    method: Option<&str>,
+    export: &str,
+    module: &str,
+) -> Result<()> {
+    let controller_path = controller
+        .dir(&project.src_dir()?.join("components"))
+        .join(format!("{}.controller.ts", controller.kebab()));
+
+    let content = fs::read_to_string(&controller_path).map_err(|_| {
+        DolphError::Usage(format!(
+            "there is no `{}` controller to apply {} to, generate it with `--controller {}`",
+            controller.path(),
+            export,
+            controller.path()
+        ))
+    })?;
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let decorator = match method {
+        None => {
+            let route = lines
+                .iter()
+                .position(|line| line.starts_with("@Route("))
+                .ok_or_else(|| {
+                    DolphError::Usage(format!(
+                        "the `{}` controller has no `@Route` decorator to apply {} next to",
+                        controller.path(),
+                        export
+                    ))
+                })?;
+
+            match lines[..route]
+                .iter()
+                .position(|line| line.starts_with("@Shield("))
+            {
+                Some(shield) if !lines[shield].contains(export) => {
+                    let line = &mut lines[shield];
+                    line.truncate(line.trim_end().len() - 1);
+                    line.push_str(&format!(", {})", export));
+                }
+                Some(_) => {}
+                None => lines.insert(route, format!("@Shield({})", export)),
+            }
+            "Shield"
+        }
+        Some(method) => {
+            let handler = lines
+                .iter()
+                .position(|line| {
+                    let line = line.trim_start();
+                    line.starts_with(&format!("async {}(", method))
+                        || line.starts_with(&format!("async {} (", method))
+                })
+                .ok_or_else(|| {
+                    DolphError::Usage(format!(
+                        "the `{}` controller has no `{}` handler",
+                        controller.path(),
+                        method
+                    ))
+                })?;
+
+            let decorator = format!("@UseMiddleware({})", export);
+            if !lines[handler.saturating_sub(3)..handler]
+                .iter()
+                .any(|line| line.trim() == decorator)
+            {
+                let indent =
+                    &lines[handler][..lines[handler].len() - lines[handler].trim_start().len()];
+                lines.insert(handler, format!("{}{}", indent, decorator));
+            }
+            "UseMiddleware"
+        }
+    };
+
+    let content = add_import(&lines.join("\n"), decorator, "@dolphjs/dolph/decorators");
+    let content = add_import(&content, export, module);
+
+    write_file(project.changes(), &controller_path, content)
+}
+
+/// The directory of shared code like middlewares, under `shared/` for spring routing like the
+/// aliases say.
+pub(super) fn shared_directory(project: &Project, directory: &str) -> Result<PathBuf> {
+    let base_directory = project.src_dir()?;
+
+    Ok(if project.config().routing == Routing::Spring {
+        base_directory.join("shared").join(directory)
+    } else {
+        base_directory.join(directory)
+    })
+}
+
+/// The file of `name` in the shared `directory`, with the `kind` suffix.
+pub(super) fn shared_file(
+    project: &Project,
+    directory: &str,
+    name: &ResourceName,
+    kind: &str,
+) -> Result<PathBuf> {
+    let directory = name.namespace_dir(&shared_directory(project, directory)?);
+
+    ensure_directory_exists(&directory)?;
+
+    Ok(directory.join(format!("{}.{}.ts", name.kebab(), kind)))
+}
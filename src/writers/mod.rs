@@ -3,32 +3,51 @@ mod config_writers;
 mod controller_writer;
 mod datasource_config_writer;
 mod db_config_writer;
+mod docker_writer;
 mod dto_writer;
 mod entity_writer;
+mod env_writer;
 mod graphql_server_writer;
 mod input_writer;
+mod middleware_writer;
 mod model_writer;
+mod package_writer;
 mod resolver_writer;
 mod service_writer;
 mod setup_writer;
-mod socket_writer;
+mod shared_writer;
 mod socket_component_writer;
+mod socket_writer;
 
 pub use config_writers::{
-    write_dolph_config, write_gitignore, write_package_json, write_spring_server_file, write_swcrc,
-    write_tsconfig,
+    register_graphql_server, register_spring_component, register_spring_socket,
+    update_server_database, write_dolph_config, write_gitignore, write_spring_server_file,
+    write_swcrc, write_tsconfig, MergePolicy,
 };
 
 pub use component_writer::write_spring_component;
 pub use controller_writer::write_spring_controller;
-pub use datasource_config_writer::write_datasource_config;
+pub use datasource_config_writer::{
+    register_entity, update_datasource_config, write_datasource_config,
+};
 pub use db_config_writer::write_db_config;
+pub use docker_writer::write_docker_files;
 pub use dto_writer::write_spring_dto;
 pub use entity_writer::write_entity;
+pub use env_writer::{env_config_file, update_env_config, write_env_config, write_env_files};
 pub use graphql_server_writer::write_graphql_server_file;
 pub use input_writer::write_input;
+pub use middleware_writer::{
+    apply_to_controller, middleware_import, write_middleware, write_shield,
+};
 pub use model_writer::write_spring_model;
+pub use package_writer::{add_dependencies, write_package_json};
 pub use resolver_writer::write_resolver;
 pub use service_writer::{write_graphql_service, write_spring_service};
-pub use setup_writer::write_setup_file;
+pub use setup_writer::{register_resolver, write_setup_file};
+pub use shared_writer::{shared_import, write_shared_file, SharedKind};
+pub use socket_component_writer::write_socket_component;
 pub use socket_writer::write_socket_service;
+
+#[cfg(test)]
+pub(crate) mod tests;
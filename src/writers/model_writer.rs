@@ -1,47 +1,60 @@
-use std::{error::Error, fs};
+use crate::config::Database;
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, Field, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
+pub fn write_spring_model(project: &Project, name: &ResourceName, fields: &[Field]) -> Result<()> {
+    let database = project.config().database;
 
-use super::config_writers::{find_base_directory, Database};
-
-pub fn write_spring_model(db: &str, name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let model_path = component_path.join(format!("{}", name));
+    let model_path = name.dir(&component_path);
 
     ensure_directory_exists(&model_path)?;
 
-    let index_path = model_path.join(format!("{}.model.ts", name));
-
-    let capitalized_name = capitalize_first_letter(name);
+    let index_path = model_path.join(format!("{}.model.ts", name.kebab()));
 
-    let database = Database::from_str(db).ok_or_else(|| "Invalid database type")?;
+    let capitalized_name = name.pascal();
+    let table_name = name.table_name(project.config().naming);
 
-    let import_statement = match database.clone() {
-        Database::MySQL => format!(
-            r#"import {{ sequelizeInstance }} from "@/shared/configs/db.config";
-import {{ DataTypes }} from "sequelize";"#
-        ),
-        Database::MongoDB => format!(r#"import {{ Schema, Document, model }} from "mongoose";"#),
+    let import_statement = match database {
+        Database::MySQL => r#"import { sequelizeInstance } from "@/shared/configs/db.config";
+import { DataTypes } from "sequelize";"#
+            .to_string(),
+        Database::MongoDB => r#"import { Schema, Document, model } from "mongoose";"#.to_string(),
         Database::PostgreSQL => r#""#.to_string(),
-        Database::None => r#""#.to_string(),
+        Database::Other => r#""#.to_string(),
     };
 
-    let other_file_content = match database.clone() {
+    let sequelize_fields: String = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "  {}: {{\n    type: {},\n    allowNull: false,\n  }},\n",
+                field.name,
+                field.kind.sequelize_type()
+            )
+        })
+        .collect();
+
+    let other_file_content = match database {
         Database::MySQL => format!(
-            r#"export const {capitalized_name} = sequelizeInstance.define("{name}", {{
+            r#"export const {capitalized_name}Model = sequelizeInstance.define("{capitalized_name}", {{
   id: {{
     type: DataTypes.INTEGER,
     allowNull: false,
     primaryKey: true,
     autoIncrement: true,
-    }},
-}});"#
+  }},
+{sequelize_fields}}}, {{ tableName: "{table_name}" }});"#
         ),
+        Database::MongoDB if !fields.is_empty() => {
+            mongoose_model(&capitalized_name, &table_name, fields)
+        }
         Database::MongoDB => format!(
             r#"export interface I{capitalized_name} extends Document {{}};
  
@@ -50,20 +63,44 @@ import {{ DataTypes }} from "sequelize";"#
 
     }});
 
-export const {capitalized_name}Model = model<I{capitalized_name}>("{name}", {capitalized_name}Schema);
+export const {capitalized_name}Model = model<I{capitalized_name}>("{capitalized_name}", {capitalized_name}Schema, "{table_name}");
  "#
         ),
         Database::PostgreSQL => r#""#.to_string(),
-        Database::None => r#""#.to_string(),
+        Database::Other => r#""#.to_string(),
     };
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing model file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn mongoose_model(capitalized_name: &str, table_name: &str, fields: &[Field]) -> String {
+    let interface_fields: String = fields
+        .iter()
+        .map(|field| format!("  {}: {};\n", field.name, field.kind.ts_type()))
+        .collect();
+    let schema_fields: String = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "    {}: {{ type: {}, required: true }},\n",
+                field.name,
+                field.kind.mongoose_type()
+            )
+        })
+        .collect();
+
+    format!(
+        r#"export interface I{capitalized_name} extends Document {{
+{interface_fields}}}
+
+const {capitalized_name}Schema = new Schema(
+  {{
+{schema_fields}  }},
+  {{ timestamps: true }},
+);
+
+export const {capitalized_name}Model = model<I{capitalized_name}>("{capitalized_name}", {capitalized_name}Schema, "{table_name}");"#
+    )
 }
@@ -0,0 +1,220 @@
+use std::fs;
+
+use serde_json::{json, Map, Value};
+
+use crate::config::{Api, Database, DefaultConfig, Language, Routing};
+use crate::error::{DolphError, Result};
+use crate::project::Project;
+use crate::utils::write_file;
+
+use super::config_writers::{write_merged_json, MergePolicy};
+
+/// An entry of package.json, included when the config matches every condition it sets.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    name: &'static str,
+    value: &'static str,
+    api: Option<Api>,
+    routing: Option<Routing>,
+    language: Option<Language>,
+    /// Empty for every database.
+    databases: &'static [Database],
+    /// A condition the others cannot express.
+    when: Option<fn(&DefaultConfig) -> bool>,
+}
+
+impl Entry {
+    const fn new(name: &'static str, value: &'static str) -> Self {
+        Self {
+            name,
+            value,
+            api: None,
+            routing: None,
+            language: None,
+            databases: &[],
+            when: None,
+        }
+    }
+
+    const fn api(mut self, api: Api) -> Self {
+        self.api = Some(api);
+        self
+    }
+
+    const fn routing(mut self, routing: Routing) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    const fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    const fn databases(mut self, databases: &'static [Database]) -> Self {
+        self.databases = databases;
+        self
+    }
+
+    const fn when(mut self, when: fn(&DefaultConfig) -> bool) -> Self {
+        self.when = Some(when);
+        self
+    }
+
+    fn applies(&self, config: &DefaultConfig) -> bool {
+        self.api.is_none_or(|api| config.serves(api))
+            && self.routing.is_none_or(|routing| routing == config.routing)
+            && self
+                .language
+                .is_none_or(|language| language == config.language)
+            && (self.databases.is_empty() || self.databases.contains(&config.database))
+            && self.when.is_none_or(|when| when(config))
+    }
+}
+
+/// Packages imported by the generated server, components, graphql setup and database configs.
+const DEPENDENCIES: &[Entry] = &[
+    Entry::new("@dolphjs/dolph", "^2.0.1"),
+    Entry::new("class-validator", "^0.14.1"),
+    // The env config loads .env with dotenv
+    Entry::new("dotenv", "^16.4.5"),
+    Entry::new("class-transformer", "^0.5.1").api(Api::Rest),
+    // Generated sockets type their events with socket.io
+    Entry::new("socket.io", "^4.7.5")
+        .api(Api::Rest)
+        .routing(Routing::Spring),
+    Entry::new("@dolphjs/graphql", "^0.2.0").api(Api::Graphql),
+    Entry::new("graphql-scalars", "^1.23.0").api(Api::Graphql),
+    Entry::new("type-graphql", "^2.0.0-rc.2").api(Api::Graphql),
+    Entry::new("typeorm", "^0.3.20").api(Api::Graphql),
+    // Database drivers, rest projects talk to them through mongoose and sequelize and graphql
+    // projects through typeorm, which falls back to postgres for other databases
+    Entry::new("mongoose", "^8.3.2")
+        .api(Api::Rest)
+        .databases(&[Database::MongoDB]),
+    Entry::new("mongodb", "^5.9.2")
+        .api(Api::Graphql)
+        .databases(&[Database::MongoDB]),
+    Entry::new("sequelize", "^6.37.3")
+        .api(Api::Rest)
+        .databases(&[Database::MySQL]),
+    Entry::new("mysql2", "^3.9.7").databases(&[Database::MySQL]),
+    Entry::new("pg", "^8.11.5").when(|config| {
+        config.database == Database::PostgreSQL
+            || (config.database == Database::Other && config.serves(Api::Graphql))
+    }),
+];
+
+/// Packages only needed to compile typescript.
+const DEV_DEPENDENCIES: &[Entry] = &[
+    Entry::new("@swc/cli", "^0.1.62").language(Language::Ts),
+    Entry::new("@swc/core", "^1.3.91").language(Language::Ts),
+    Entry::new("@types/express", "^4.17.21").language(Language::Ts),
+    Entry::new("@types/node", "^20.8.2").language(Language::Ts),
+    Entry::new("ts-node", "^10.9.1").language(Language::Ts),
+    Entry::new("tsc-alias", "^1.8.8").language(Language::Ts),
+    Entry::new("tsconfig-paths", "^4.2.0").language(Language::Ts),
+    Entry::new("typescript", "^5.2.2").language(Language::Ts),
+];
+
+/// Javascript projects run their sources as they are, so only typescript ones get build scripts.
+const SCRIPTS: &[Entry] = &[
+    Entry::new("dev:start", "dolph watch"),
+    Entry::new(
+        "dev:docker:start",
+        "docker-compose -f docker-compose-dev.yml up",
+    ),
+    Entry::new(
+        "dev:docker:stop",
+        "docker-compose -f docker-compose-dev.yml down",
+    ),
+    Entry::new("build", "dolph build").language(Language::Ts),
+    Entry::new("build:tsc", "tsc && tsc-alias").language(Language::Ts),
+    Entry::new("start", "dolph start"),
+    Entry::new("clean", "rm -r app && rm -r logs").language(Language::Ts),
+    Entry::new("clean", "rm -r logs").language(Language::Js),
+];
+
+/// The entries of `table` that apply to `config`, as a JSON object.
+fn compose(table: &[Entry], config: &DefaultConfig) -> Map<String, Value> {
+    table
+        .iter()
+        .filter(|entry| entry.applies(config))
+        .map(|entry| (entry.name.to_string(), json!(entry.value)))
+        .collect()
+}
+
+pub fn write_package_json(project: &Project, project_name: &str, merge: MergePolicy) -> Result<()> {
+    let config = project.config();
+    let file_path = project.root().join("package.json");
+
+    let mut manifest = json!({
+      "name": project_name.to_string(),
+      "version": "1.0.0",
+      "main": config.language.entry_file(),
+      "author": "",
+      "license": "MIT",
+      "engines": {
+        "node": ">=18.0.0"
+      },
+      "scripts": compose(SCRIPTS, config),
+      "dependencies": compose(DEPENDENCIES, config)
+    });
+
+    let dev_dependencies = compose(DEV_DEPENDENCIES, config);
+    if !dev_dependencies.is_empty() {
+        manifest["devDependencies"] = Value::Object(dev_dependencies);
+    }
+
+    // The project's own name, version and dependency versions win over the generated ones
+    write_merged_json(
+        project,
+        &file_path,
+        &manifest,
+        &[
+            "name",
+            "version",
+            "author",
+            "license",
+            "dependencies",
+            "devDependencies",
+        ],
+        merge,
+    )
+}
+
+/// Adds the packages the configured api and database need to an existing package.json, keeping
+/// the versions of those already declared. Returns the added packages.
+pub fn add_dependencies(project: &Project) -> Result<Vec<&'static str>> {
+    let file_path = project.root().join("package.json");
+
+    let content = fs::read_to_string(&file_path).map_err(|e| DolphError::write(&file_path, e))?;
+    let mut manifest: Value = serde_json::from_str(&content)
+        .map_err(|e| DolphError::Usage(format!("package.json is not valid JSON: {}", e)))?;
+
+    let mut added = Vec::new();
+    for (key, table) in [
+        ("dependencies", DEPENDENCIES),
+        ("devDependencies", DEV_DEPENDENCIES),
+    ] {
+        for entry in table.iter().filter(|entry| entry.applies(project.config())) {
+            if !manifest[key].is_object() {
+                manifest[key] = json!({});
+            }
+            let declared = manifest[key].as_object_mut().unwrap();
+            if !declared.contains_key(entry.name) {
+                declared.insert(entry.name.to_string(), json!(entry.value));
+                added.push(entry.name);
+            }
+        }
+    }
+
+    if !added.is_empty() {
+        write_file(
+            project.changes(),
+            &file_path,
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+    }
+    Ok(added)
+}
@@ -1,18 +1,15 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::find_base_directory;
-
-pub fn write_resolver(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory =
-        find_base_directory().ok_or_else(|| "Could not find the base directory")?;
+pub fn write_resolver(project: &Project, name: &ResourceName, crud: bool) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let name_path = component_path.join(format!("{}", name));
+    let name_path = name.dir(&component_path);
 
     ensure_directory_exists(&name_path)?;
 
@@ -20,13 +17,21 @@ pub fn write_resolver(name: &str) -> Result<(), Box<dyn Error>> {
 
     ensure_directory_exists(&resolver_path)?;
 
-    let index_path = resolver_path.join(format!("{}.resolver.ts", name));
+    let index_path = resolver_path.join(format!("{}.resolver.ts", name.kebab()));
+
+    let capitalized_name = name.pascal();
+    let camel_name = name.camel();
 
-    let capitalized_name = capitalize_first_letter(name);
+    if crud {
+        return write_file(
+            project.changes(),
+            &index_path,
+            crud_file_content(name, &capitalized_name),
+        );
+    }
 
-    let import_statement = r#"import { Mutation, Query, Resolver } from "type-graphql";
-    "#
-    .to_string();
+    let import_statement =
+        r#"import { Mutation, Query, Resolver } from "type-graphql";"#.to_string();
 
     let other_file_content = format!(
         r#"@Resolver()
@@ -37,24 +42,74 @@ export class {capitalized_name}Resolver{{
         // You should instantiate the service here
     }}
 
-    @Mutation()
-    async create{capitalized_name}() {{
-        
+    @Mutation(() => Boolean)
+    async create{capitalized_name}(): Promise<boolean> {{
+        return true;
     }}
 
-    @Query()
-    async fetch{capitalized_name}() {{}}
+    @Query(() => String)
+    async fetch{capitalized_name}(): Promise<string> {{
+        return "you've reached the {camel_name} resolver.";
+    }}
 }}
 "#
     );
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing resolver file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn crud_file_content(name: &ResourceName, capitalized_name: &str) -> String {
+    let file_name = name.kebab();
+    let camel_name = name.camel();
+
+    format!(
+        r#"import {{ Arg, Args, ID, Mutation, Query, Resolver }} from "type-graphql";
+import {{ {capitalized_name}, Paginated{capitalized_name} }} from "../entities/{file_name}.entity";
+import {{
+    Create{capitalized_name}Input,
+    Paginate{capitalized_name}Args,
+    Update{capitalized_name}Input
+}} from "../inputs/{file_name}.input";
+import {{ {capitalized_name}Service }} from "../services/{file_name}.service";
+
+@Resolver(() => {capitalized_name})
+export class {capitalized_name}Resolver {{
+    private {camel_name}Service: {capitalized_name}Service;
+
+    constructor() {{
+        this.{camel_name}Service = new {capitalized_name}Service();
+    }}
+
+    @Mutation(() => {capitalized_name})
+    async create{capitalized_name}(@Arg("data") data: Create{capitalized_name}Input): Promise<{capitalized_name}> {{
+        return this.{camel_name}Service.create{capitalized_name}(data);
+    }}
+
+    @Mutation(() => {capitalized_name}, {{ nullable: true }})
+    async update{capitalized_name}(
+        @Arg("id", () => ID) id: string,
+        @Arg("data") data: Update{capitalized_name}Input
+    ): Promise<{capitalized_name} | null> {{
+        return this.{camel_name}Service.update{capitalized_name}(id, data);
+    }}
+
+    @Mutation(() => Boolean)
+    async delete{capitalized_name}(@Arg("id", () => ID) id: string): Promise<boolean> {{
+        return this.{camel_name}Service.delete{capitalized_name}(id);
+    }}
+
+    @Query(() => {capitalized_name}, {{ nullable: true }})
+    async {camel_name}(@Arg("id", () => ID) id: string): Promise<{capitalized_name} | null> {{
+        return this.{camel_name}Service.find{capitalized_name}(id);
+    }}
+
+    @Query(() => Paginated{capitalized_name})
+    async {camel_name}List(@Args() {{ page, limit }}: Paginate{capitalized_name}Args): Promise<Paginated{capitalized_name}> {{
+        return this.{camel_name}Service.list{capitalized_name}(page, limit);
+    }}
+}}
+"#
+    )
 }
@@ -1,83 +1,89 @@
-use std::{error::Error, fs};
+use crate::config::Database;
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
+pub fn write_spring_service(project: &Project, name: &ResourceName, crud: bool) -> Result<()> {
+    let database = project.config().database;
 
-use super::config_writers::{find_base_directory, Database};
-
-pub fn write_spring_service(db: &str, name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let service_path = component_path.join(format!("{}", name));
+    let service_path = name.dir(&component_path);
 
     ensure_directory_exists(&service_path)?;
 
-    let index_path = service_path.join(format!("{}.service.ts", name));
-
-    let capitalized_name = capitalize_first_letter(name);
+    let index_path = service_path.join(format!("{}.service.ts", name.kebab()));
 
-    let database = Database::from_str(db).ok_or_else(|| "Invalid database type")?;
+    let capitalized_name = name.pascal();
+    let file_name = name.kebab();
+    let camel_name = name.camel();
+    let crud = crud && project.config().supports_crud();
 
-    let import_statement = match database.clone() {
+    let mut import_statement = match database {
         Database::MySQL => format!(
             r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
 import {{ Dolph }} from "@dolphjs/dolph/common";
 import {{ InjectMySQL }} from "@dolphjs/dolph/decorators";
 import {{ ModelStatic, Model }} from "sequelize";
-import {{ {name}Model }} from "./{name}.model"; "#
+import {{ {capitalized_name}Model }} from "./{file_name}.model";"#
         ),
         Database::MongoDB => format!(
             r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
 import {{ Dolph }} from "@dolphjs/dolph/common";
 import {{ InjectMongo }} from "@dolphjs/dolph/decorators";
 import {{ Model }} from "mongoose";
-import {{ {capitalized_name}Model, I{capitalized_name} }} from "./{name}.model"; "#
-        ),
-        Database::PostgreSQL => format!(
-            r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
-import {{ Dolph }} from "@dolphjs/dolph/common";"#
-        ),
-        Database::None => format!(
-            r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
-import {{ Dolph }} from "@dolphjs/dolph/common";"#
+import {{ {capitalized_name}Model, I{capitalized_name} }} from "./{file_name}.model";"#
         ),
+        Database::PostgreSQL => r#"import { DolphServiceHandler } from "@dolphjs/dolph/classes";
+import { Dolph } from "@dolphjs/dolph/common";"#
+            .to_string(),
+        Database::Other => r#"import { DolphServiceHandler } from "@dolphjs/dolph/classes";
+import { Dolph } from "@dolphjs/dolph/common";"#
+            .to_string(),
+    };
+
+    if crud {
+        import_statement.push_str(&format!(
+            r#"
+import {{ Create{capitalized_name}Dto, Update{capitalized_name}Dto }} from "./{file_name}.dto";"#
+        ));
+    }
+
+    let methods = if crud {
+        crud_methods(database, name, &capitalized_name)
+    } else {
+        String::new()
     };
 
-    let other_file_content = match database.clone() {
+    let other_file_content = match database {
         Database::MongoDB => format!(
-            r#"@InjectMongo("{name}Model", {capitalized_name}Model)
+            r#"@InjectMongo("{camel_name}Model", {capitalized_name}Model)
 export class {capitalized_name}Service extends DolphServiceHandler<Dolph>{{
-    private {name}Model!: Model<I{capitalized_name}>;
+    private {camel_name}Model!: Model<I{capitalized_name}>;
 
     constructor() {{
-        super("{name}Service");
-    }}
+        super("{camel_name}Service");
+    }}{methods}
 }}"#
         ),
         Database::MySQL => format!(
-            r#"@InjectMySQL("{name}Model", )
+            r#"@InjectMySQL("{camel_name}Model", {capitalized_name}Model)
 export class {capitalized_name}Service extends DolphServiceHandler<Dolph>{{
-    private {name}Model!: ModelStatic<Model<any, any>>;
+    private {camel_name}Model!: ModelStatic<Model<any, any>>;
 
     constructor() {{
-        super("{name}Service");
-    }}
+        super("{camel_name}Service");
+    }}{methods}
 }}"#
         ),
-        Database::PostgreSQL => format!(
+        Database::PostgreSQL | Database::Other => format!(
             r#"export class {capitalized_name}Service extends DolphServiceHandler<Dolph>{{
     constructor() {{
-        super("{name}Service");
-    }}
-}}"#
-        ),
-        Database::None => format!(
-            r#"export class {capitalized_name}Service extends DolphServiceHandler<Dolph>{{
-    constructor() {{
-        super("{name}Service");
+        super("{camel_name}Service");
     }}
 }}"#
         ),
@@ -85,23 +91,89 @@ export class {capitalized_name}Service extends DolphServiceHandler<Dolph>{{
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing service file: {}", e));
-            Err(Box::new(e))
-        }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+fn crud_methods(database: Database, name: &ResourceName, capitalized_name: &str) -> String {
+    let camel_name = name.camel();
+
+    match database {
+        Database::MongoDB => format!(
+            r#"
+
+    async create(data: Create{capitalized_name}Dto) {{
+        return this.{camel_name}Model.create(data);
+    }}
+
+    async findAll(page: number, limit: number) {{
+        const [items, total] = await Promise.all([
+            this.{camel_name}Model.find().skip((page - 1) * limit).limit(limit),
+            this.{camel_name}Model.countDocuments(),
+        ]);
+
+        return {{ items, total, page, limit }};
+    }}
+
+    async findById(id: string) {{
+        return this.{camel_name}Model.findById(id);
+    }}
+
+    async update(id: string, data: Update{capitalized_name}Dto) {{
+        return this.{camel_name}Model.findByIdAndUpdate(id, data, {{ new: true }});
+    }}
+
+    async delete(id: string) {{
+        return this.{camel_name}Model.findByIdAndDelete(id);
+    }}"#
+        ),
+        Database::MySQL => format!(
+            r#"
+
+    async create(data: Create{capitalized_name}Dto) {{
+        return this.{camel_name}Model.create({{ ...data }});
+    }}
+
+    async findAll(page: number, limit: number) {{
+        const {{ rows, count }} = await this.{camel_name}Model.findAndCountAll({{
+            offset: (page - 1) * limit,
+            limit,
+        }});
+
+        return {{ items: rows, total: count, page, limit }};
+    }}
+
+    async findById(id: string) {{
+        return this.{camel_name}Model.findByPk(id);
+    }}
+
+    async update(id: string, data: Update{capitalized_name}Dto) {{
+        const record = await this.{camel_name}Model.findByPk(id);
+        if (!record) return null;
+
+        return record.update({{ ...data }});
+    }}
+
+    async delete(id: string) {{
+        const record = await this.{camel_name}Model.findByPk(id);
+        if (!record) return null;
+
+        await record.destroy();
+        return record;
+    }}"#
+        ),
+        // Rejected by the generator, there is no model to query
+        Database::PostgreSQL | Database::Other => String::new(),
     }
 }
 
-pub fn write_graphql_service(name: &str) -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_graphql_service(project: &Project, name: &ResourceName, crud: bool) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let component_path = base_directory.join("components");
 
     ensure_directory_exists(&component_path)?;
 
-    let name_directory = component_path.join(format!("{}", name));
+    let name_directory = name.dir(&component_path);
 
     ensure_directory_exists(&name_directory)?;
 
@@ -109,14 +181,24 @@ pub fn write_graphql_service(name: &str) -> Result<(), Box<dyn Error>> {
 
     ensure_directory_exists(&service_path)?;
 
-    let index_path = service_path.join(format!("{}.service.ts", name));
+    let index_path = service_path.join(format!("{}.service.ts", name.kebab()));
 
-    let capitalized_name = capitalize_first_letter(name);
+    let capitalized_name = name.pascal();
+    let file_name = name.kebab();
+    let camel_name = name.camel();
+
+    if crud {
+        return write_file(
+            project.changes(),
+            &index_path,
+            graphql_crud_file_content(name, &capitalized_name, project.config().database),
+        );
+    }
 
     let import_statement = format!(
         r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
 import {{ Dolph }} from "@dolphjs/dolph/common";
-import {{ Create{capitalized_name}Input }} from "../inputs/{name}.inputs";
+import {{ Create{capitalized_name}Input }} from "../inputs/{file_name}.input";
 "#
     );
 
@@ -125,7 +207,7 @@ import {{ Create{capitalized_name}Input }} from "../inputs/{name}.inputs";
 // Your repository should be here
 
     constructor() {{
-        super("{name}Service");
+        super("{camel_name}Service");
         // Your repository should be initialized here
     }}
 
@@ -136,11 +218,87 @@ import {{ Create{capitalized_name}Input }} from "../inputs/{name}.inputs";
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing service file: {}", e));
-            Err(Box::new(e))
-        }
-    }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+/// A service backed by the typeorm repository of the generated entity.
+fn graphql_crud_file_content(
+    name: &ResourceName,
+    capitalized_name: &str,
+    database: Database,
+) -> String {
+    let file_name = name.kebab();
+    let camel_name = name.camel();
+
+    // MongoDB entities are keyed by an ObjectId, and typeorm does not count deleted documents
+    let (imports, criteria, delete) = if database == Database::MongoDB {
+        (
+            "import { ObjectId } from \"mongodb\";\nimport { Repository } from \"typeorm\";",
+            "{ _id: new ObjectId(id) }",
+            format!(
+                r#"const {camel_name} = await this.{camel_name}Repository.findOneBy({{ _id: new ObjectId(id) }});
+        if (!{camel_name}) return false;
+
+        await this.{camel_name}Repository.remove({camel_name});
+        return true;"#
+            ),
+        )
+    } else {
+        (
+            "import { Repository } from \"typeorm\";",
+            "{ id }",
+            format!(
+                r#"const result = await this.{camel_name}Repository.delete(id);
+        return !!result.affected;"#
+            ),
+        )
+    };
+
+    format!(
+        r#"import {{ DolphServiceHandler }} from "@dolphjs/dolph/classes";
+import {{ Dolph }} from "@dolphjs/dolph/common";
+{imports}
+import {{ AppDataSource }} from "@/shared/configs/data_source";
+import {{ {capitalized_name}, Paginated{capitalized_name} }} from "../entities/{file_name}.entity";
+import {{ Create{capitalized_name}Input, Update{capitalized_name}Input }} from "../inputs/{file_name}.input";
+
+export class {capitalized_name}Service extends DolphServiceHandler<Dolph> {{
+    private {camel_name}Repository: Repository<{capitalized_name}>;
+
+    constructor() {{
+        super("{camel_name}Service");
+        this.{camel_name}Repository = AppDataSource.getRepository({capitalized_name});
+    }}
+
+    async create{capitalized_name}(data: Create{capitalized_name}Input): Promise<{capitalized_name}> {{
+        const {camel_name} = this.{camel_name}Repository.create(data);
+        return this.{camel_name}Repository.save({camel_name});
+    }}
+
+    async find{capitalized_name}(id: string): Promise<{capitalized_name} | null> {{
+        return this.{camel_name}Repository.findOneBy({criteria});
+    }}
+
+    async list{capitalized_name}(page: number, limit: number): Promise<Paginated{capitalized_name}> {{
+        const [items, total] = await this.{camel_name}Repository.findAndCount({{
+            skip: (page - 1) * limit,
+            take: limit,
+        }});
+        return {{ items, total, page, limit }};
+    }}
+
+    async update{capitalized_name}(id: string, data: Update{capitalized_name}Input): Promise<{capitalized_name} | null> {{
+        const {camel_name} = await this.{camel_name}Repository.findOneBy({criteria});
+        if (!{camel_name}) return null;
+
+        this.{camel_name}Repository.merge({camel_name}, data);
+        return this.{camel_name}Repository.save({camel_name});
+    }}
+
+    async delete{capitalized_name}(id: string): Promise<boolean> {{
+        {delete}
+    }}
+}}
+"#
+    )
 }
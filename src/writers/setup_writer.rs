@@ -1,43 +1,67 @@
-use std::{error::Error, fs};
+use std::fs;
 
-use super::config_writers::find_base_directory;
+use crate::error::{DolphError, Result};
+use crate::output::message;
+use crate::project::Project;
+use crate::utils::{add_import, add_to_array, write_file, ResourceName};
 
-pub fn write_setup_file() -> Result<(), Box<dyn Error>> {
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_setup_file(project: &Project) -> Result<()> {
+    let index_path = project.src_dir()?.join("setup.ts");
 
-    let index_path = base_directory.join("setup.ts");
+    let import_statement =
+        r#"import { buildSchema, NonEmptyArray } from "type-graphql";"#.to_string();
 
-    let import_statement = format!(
-        r#"import {{ buildSchema }} from "type-graphql";
-        "#
-    );
+    let other_file_content = r#"// Generated resolvers are added here, the schema can only be built once there is at least one
+const resolvers = [] as unknown as NonEmptyArray<Function>;
 
-    let other_file_content = format!(
-        r#"export const schema = async function createSchema() {{
-return await buildSchema({{
-    // Your resolvers should go here
-    resolvers: [],
+export const schema = async function createSchema() {
+return await buildSchema({
+    resolvers,
     validate: true,
-    }});
-}};
+    });
+};
 
 // Your context definition goes here
-export const context = async ({{ req, res }}) => {{
+export const context = async ({ req, res }) => {
   const session = req.session;
   const cookies = req.cookies;
 
-  return {{ req, res, session, cookies }};
-}};
+  return { req, res, session, cookies };
+};
     "#
-    );
+    .to_string();
 
     let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
 
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing setup file: {}", e));
-            Err(Box::new(e))
-        }
+    write_file(project.changes(), &index_path, file_content)
+}
+
+/// Adds the resolver generated for `name` to the `resolvers` the schema of `setup.ts` is built
+/// from.
+pub fn register_resolver(project: &Project, name: &ResourceName) -> Result<()> {
+    let path = project.src_dir()?.join("setup.ts");
+    if !path.exists() {
+        write_setup_file(project)?;
     }
+    let content = fs::read_to_string(&path).map_err(|e| DolphError::write(&path, e))?;
+
+    let resolver = format!("{}Resolver", name.pascal());
+    let Some(content) = add_to_array(&content, "const resolvers = [", &resolver) else {
+        message(format!(
+            "Could not find `const resolvers = [...]` in setup.ts, register {} there manually",
+            resolver
+        ));
+        return Ok(());
+    };
+
+    let module = format!(
+        "./components/{}/resolvers/{}.resolver",
+        name.path(),
+        name.kebab()
+    );
+    write_file(
+        project.changes(),
+        &path,
+        add_import(&content, &resolver, &module),
+    )
 }
@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DolphError, Result};
+use crate::project::Project;
+use crate::utils::{write_file, Field, ResourceName};
+
+use super::middleware_writer::{shared_directory, shared_file};
+
+/// The shared code that lives next to the components, each kind in its own directory with an
+/// `index.ts` barrel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedKind {
+    Decorator,
+    Helper,
+    Constant,
+    Interface,
+    Validation,
+}
+
+impl SharedKind {
+    /// The file suffix, e.g. `user.interface.ts`.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            SharedKind::Decorator => "decorator",
+            SharedKind::Helper => "helper",
+            SharedKind::Constant => "constant",
+            SharedKind::Interface => "interface",
+            SharedKind::Validation => "validation",
+        }
+    }
+
+    /// The directory, which is also the name of its path alias.
+    pub fn directory(&self) -> &'static str {
+        match self {
+            SharedKind::Decorator => "decorators",
+            SharedKind::Helper => "helpers",
+            SharedKind::Constant => "constants",
+            SharedKind::Interface => "interfaces",
+            SharedKind::Validation => "validations",
+        }
+    }
+
+    /// The name the file exports.
+    pub fn export_name(&self, name: &ResourceName) -> String {
+        match self {
+            SharedKind::Decorator | SharedKind::Interface => name.pascal(),
+            SharedKind::Helper => name.camel(),
+            SharedKind::Constant => name.snake().to_uppercase(),
+            // `is-slug` stays `IsSlug` rather than becoming `IsIsSlug`
+            SharedKind::Validation if name.kebab().starts_with("is-") => name.pascal(),
+            SharedKind::Validation => format!("Is{}", name.pascal()),
+        }
+    }
+}
+
+/// Writes a decorator, helper, constant, interface or validation and exports it from the barrel of
+/// its directory.
+pub fn write_shared_file(
+    project: &Project,
+    kind: SharedKind,
+    name: &ResourceName,
+    fields: &[Field],
+) -> Result<()> {
+    let index_path = shared_file(project, kind.directory(), name, kind.suffix())?;
+
+    let export_name = kind.export_name(name);
+    let kebab_name = name.kebab();
+
+    let file_content = match kind {
+        SharedKind::Decorator => format!(
+            r#"/** Wraps the decorated method, use it as `@{export_name}()`. */
+export function {export_name}(): MethodDecorator {{
+  return (target, propertyKey, descriptor: PropertyDescriptor) => {{
+    const original = descriptor.value;
+
+    descriptor.value = function (...args: unknown[]) {{
+      // Runs around every call of the decorated method
+      return original.apply(this, args);
+    }};
+
+    return descriptor;
+  }};
+}}
+"#
+        ),
+        SharedKind::Helper => format!(
+            r#"export function {export_name}(value: string): string {{
+  // Implement the {kebab_name} helper here
+  return value;
+}}
+"#
+        ),
+        SharedKind::Constant => format!(
+            r#"export const {export_name} = {{
+  // Add the {kebab_name} values here
+}} as const;
+"#
+        ),
+        SharedKind::Interface => {
+            let properties: String = fields
+                .iter()
+                .map(|field| format!("  {}: {};\n", field.name, field.kind.ts_type()))
+                .collect();
+            let properties = if properties.is_empty() {
+                format!("  // Add the fields of {export_name} here\n")
+            } else {
+                properties
+            };
+
+            format!("export interface {export_name} {{\n{properties}}}\n")
+        }
+        SharedKind::Validation => {
+            let rule = export_name[..1].to_lowercase() + &export_name[1..];
+            format!(
+                r#"import {{ registerDecorator, ValidationArguments, ValidationOptions }} from "class-validator";
+
+/** Validates a dto property, use it as `@{export_name}()`. */
+export function {export_name}(validationOptions?: ValidationOptions) {{
+  return (object: object, propertyName: string) => {{
+    registerDecorator({{
+      name: "{rule}",
+      target: object.constructor,
+      propertyName,
+      options: validationOptions,
+      validator: {{
+        validate(value: unknown) {{
+          // Replace with the {kebab_name} rule
+          return typeof value === "string" && value.length > 0;
+        }},
+        defaultMessage(args: ValidationArguments) {{
+          return `${{args.property}} failed the {export_name} check`;
+        }},
+      }},
+    }});
+  }};
+}}
+"#
+            )
+        }
+    };
+
+    write_file(project.changes(), &index_path, file_content)?;
+
+    update_barrel(project, &shared_directory(project, kind.directory())?, kind)
+}
+
+/// The `@/` alias the barrel of `kind` is imported from, e.g. `@/helpers`.
+pub fn shared_import(kind: SharedKind) -> String {
+    format!("@/{}", kind.directory())
+}
+
+/// Rewrites `index.ts` to re-export every file of `kind` below `directory`. Lines added by hand
+/// are kept, re-exports of files that no longer exist are dropped.
+fn update_barrel(project: &Project, directory: &Path, kind: SharedKind) -> Result<()> {
+    let barrel_path = directory.join("index.ts");
+    let existing = if barrel_path.exists() {
+        fs::read_to_string(&barrel_path).map_err(|e| DolphError::write(&barrel_path, e))?
+    } else {
+        String::new()
+    };
+
+    let mut exports: Vec<String> = files_of_kind(directory, kind)
+        .iter()
+        .filter_map(|file| {
+            let module = file.strip_prefix(directory).ok()?.with_extension("");
+            Some(format!(
+                "export * from \"./{}\";",
+                module.to_string_lossy().replace('\\', "/")
+            ))
+        })
+        .collect();
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in existing.lines() {
+        let reexport = line.starts_with("export * from \"./");
+        if !reexport {
+            lines.push(line.to_string());
+        } else if line.ends_with(&format!(".{}\";", kind.suffix())) {
+            // Generated re-exports are rebuilt from the files on disk below
+            continue;
+        } else if !exports.contains(&line.to_string()) {
+            exports.push(line.to_string());
+        }
+    }
+    exports.sort();
+    exports.dedup();
+
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(exports);
+
+    write_file(
+        project.changes(),
+        &barrel_path,
+        format!("{}\n", lines.join("\n")),
+    )
+}
+
+fn files_of_kind(directory: &Path, kind: SharedKind) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let suffix = format!(".{}.ts", kind.suffix());
+    let mut files = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            files.extend(files_of_kind(&path, kind));
+        } else if path.to_string_lossy().ends_with(&suffix) {
+            files.push(path);
+        }
+    }
+
+    files
+}
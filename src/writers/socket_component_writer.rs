@@ -1 +1,28 @@
-pub fn socket_component_writer(){}
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
+
+/// Writes the `@Socket` component that hands the socket service to `DolphFactory`.
+pub fn write_socket_component(project: &Project, name: &ResourceName) -> Result<()> {
+    let base_directory = project.src_dir()?;
+
+    let socket_path = name.namespace_dir(&base_directory.join("shared").join("socket"));
+
+    ensure_directory_exists(&socket_path)?;
+
+    let index_path = socket_path.join(format!("{}.socket.component.ts", name.kebab()));
+
+    let capitalized_name = name.pascal();
+    let file_name = name.kebab();
+
+    let file_content = format!(
+        r#"import {{ Socket }} from "@dolphjs/dolph/decorators";
+import {{ {capitalized_name}SocketService }} from "./{file_name}.socket.service";
+
+@Socket({{ services: [{capitalized_name}SocketService], socketImplementations: [] }})
+export class {capitalized_name}SocketComponent {{}}
+"#
+    );
+
+    write_file(project.changes(), &index_path, file_content)
+}
@@ -1,61 +1,82 @@
-use std::{error::Error, fs};
+use crate::error::Result;
+use crate::project::Project;
+use crate::utils::{ensure_directory_exists, write_file, ResourceName};
 
-use crate::utils::{capitalize_first_letter, ensure_directory_exists};
-
-use super::config_writers::{find_base_directory};
-
-pub fn write_socket_service(name: &str) -> Result<(), Box<dyn Error>>{
-    let base_directory = find_base_directory().ok_or_else(|| "Could not find base directory")?;
+pub fn write_socket_service(project: &Project, name: &ResourceName) -> Result<()> {
+    let base_directory = project.src_dir()?;
 
     let shared_directory = base_directory.join("shared");
 
     ensure_directory_exists(&shared_directory)?;
 
-    let socket_path = shared_directory.join("socket");
+    let socket_path = name.namespace_dir(&shared_directory.join("socket"));
 
     ensure_directory_exists(&socket_path)?;
 
-    let index_path = socket_path.join(format!("{}.socket.service.ts", name));
-
-    let capitalized_name = capitalize_first_letter(name);
-
-    let import_statement = format!(
-    r#"import {{ DolphSocketServiceHandler }} from '@dolphjs/dolph/classes';
-import {{ Dolph }} from '@dolphjs/dolph/common';
-    "#);
-
-    let other_file_content = format!(
-    r#"export class {capitalized_name}SocketService extends DolphSocketServiceHandler<Dolph> {{
-        constructor(){{
-            super();
-            this.socketService;
-            this.handleEvents();
-        }}
-
-        private handleEvents(){{
-            this.socket.on("connection", (socket) => {{
-                socket.emit("connected", "connection successful");
-            }});
-        }}
-    }}
-    "#
-);
-
-    let file_content = format!("{}\n\n{}\n", import_statement, other_file_content);
-
-    match fs::write(&index_path, file_content) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", format!("Error writing socket service file: {}", e));
-            Err(Box::new(e))
-        }
-    }
-
+    let index_path = socket_path.join(format!("{}.socket.service.ts", name.kebab()));
+
+    let capitalized_name = name.pascal();
+    let file_name = name.kebab();
+
+    write_file(
+        project.changes(),
+        &socket_path.join(format!("{}.socket.events.ts", file_name)),
+        events_file_content(&capitalized_name),
+    )?;
+
+    let file_content = format!(
+        r#"import {{ DolphSocketServiceHandler }} from "@dolphjs/dolph/classes";
+import {{ Dolph }} from "@dolphjs/dolph/common";
+import {{ Socket }} from "socket.io";
+import {{
+  {capitalized_name}ClientEvents,
+  {capitalized_name}Message,
+  {capitalized_name}ServerEvents,
+}} from "./{file_name}.socket.events";
+
+type {capitalized_name}Socket = Socket<{capitalized_name}ClientEvents, {capitalized_name}ServerEvents>;
+
+export class {capitalized_name}SocketService extends DolphSocketServiceHandler<Dolph> {{
+  constructor() {{
+    super();
+    this.handleEvents();
+  }}
+
+  private handleEvents() {{
+    this.socket.on("connection", (socket: {capitalized_name}Socket) => {{
+      socket.emit("connected", "connection successful");
+
+      socket.on("message", (payload) => this.onMessage(socket, payload));
+    }});
+  }}
+
+  private onMessage(socket: {capitalized_name}Socket, payload: {capitalized_name}Message) {{
+    socket.broadcast.emit("message", payload);
+  }}
+}}
+"#
+    );
+
+    write_file(project.changes(), &index_path, file_content)
 }
 
-
-
-
-
-
-
+/// The events clients and the server exchange, so handlers and emits are type checked.
+fn events_file_content(capitalized_name: &str) -> String {
+    format!(
+        r#"export interface {capitalized_name}Message {{
+  text: string;
+}}
+
+/** Events sent by clients, with the arguments of each. */
+export interface {capitalized_name}ClientEvents {{
+  message: (payload: {capitalized_name}Message) => void;
+}}
+
+/** Events the server emits to clients, with the arguments of each. */
+export interface {capitalized_name}ServerEvents {{
+  connected: (message: string) => void;
+  message: (payload: {capitalized_name}Message) => void;
+}}
+"#
+    )
+}
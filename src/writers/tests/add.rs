@@ -0,0 +1,147 @@
+//! `dolph add` on projects generated for another config.
+
+use std::fs;
+
+use crate::add::{run_add, Integration};
+use crate::config::{Api, Database, DefaultConfig, Language, Routing};
+use crate::error::DolphError;
+use crate::output::FileChanges;
+use crate::project::Project;
+use crate::properties::{Generator, Schematic};
+use crate::utils::read_config;
+
+use super::generate_project;
+
+fn add(root: &std::path::Path, integration: Integration) -> Result<(), DolphError> {
+    let project = Project::new(root, read_config(root, &FileChanges::default()).unwrap());
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(run_add(&project, integration, None))
+}
+
+#[test]
+fn switching_databases_updates_the_project_in_place() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+    let server = fs::read_to_string(root.join("src/server.ts")).unwrap();
+
+    add(root, Integration::Mysql).unwrap();
+
+    assert_eq!(
+        read_config(root, &FileChanges::default()).unwrap().database,
+        Database::MySQL
+    );
+    let mysql_server = fs::read_to_string(root.join("src/server.ts")).unwrap();
+    assert!(mysql_server.contains("autoInitMySql(sequelizeInstance);\n\ndolph.start();"));
+    assert!(mysql_server.contains("@/shared/configs/db.config"));
+    assert!(root.join("src/shared/configs/db.config.ts").exists());
+    assert!(fs::read_to_string(root.join("package.json"))
+        .unwrap()
+        .contains("\"sequelize\""));
+    assert!(fs::read_to_string(root.join(".env"))
+        .unwrap()
+        .contains("DB_HOST=localhost"));
+    assert!(
+        fs::read_to_string(root.join("src/shared/configs/env.config.ts"))
+            .unwrap()
+            .contains("read(\"DB_HOST\")")
+    );
+
+    add(root, Integration::Mongo).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(root.join("src/server.ts")).unwrap(),
+        server
+    );
+}
+
+#[test]
+fn graphql_is_added_next_to_the_components() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MySQL);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    add(root, Integration::Graphql).unwrap();
+
+    let config = read_config(root, &FileChanges::default()).unwrap();
+    assert_eq!(config.api, Api::Rest);
+    assert!(config.serves(Api::Graphql));
+    assert_eq!(
+        fs::read_to_string(root.join("src/server.ts")).unwrap(),
+        r#"import { DolphFactory } from "@dolphjs/dolph";
+import { UserProfileComponent } from "./components/admin/user-profile/user-profile.component";
+import { sequelizeInstance } from "@/shared/configs/db.config";
+import { autoInitMySql } from "@dolphjs/dolph/packages";
+import { UserProfileSocketComponent } from "./shared/socket/admin/user-profile.socket.component";
+import { context, schema } from "./setup";
+import { AppDataSource } from "./shared/configs/data_source";
+import { logger } from "@dolphjs/dolph/utilities";
+
+const dolph = new DolphFactory([UserProfileComponent], UserProfileSocketComponent, { graphql: true, schema: schema(), context });
+autoInitMySql(sequelizeInstance);
+
+AppDataSource.initialize()
+  .then(() => {
+    dolph.start();
+  })
+  .catch((err) => logger.error(`[Dolph Error]:`, err));
+"#
+    );
+    assert!(root.join("src/shared/configs/db.config.ts").exists());
+    assert!(root.join("src/setup.ts").exists());
+    assert!(
+        fs::read_to_string(root.join("src/shared/configs/data_source.ts"))
+            .unwrap()
+            .contains("type: \"mysql\"")
+    );
+    let manifest = fs::read_to_string(root.join("package.json")).unwrap();
+    assert!(manifest.contains("\"type-graphql\""));
+    assert!(manifest.contains("\"sequelize\""));
+
+    // Both apis keep generating, with the graphql resources registered
+    let project = Project::new(root, config);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(Generator::new(project).generate(Schematic::All, "orders"))
+        .unwrap();
+    let orders = root.join("src/components/orders");
+    assert!(orders.join("orders.controller.ts").exists());
+    assert!(orders.join("resolvers/orders.resolver.ts").exists());
+    assert!(fs::read_to_string(root.join("src/setup.ts"))
+        .unwrap()
+        .contains("OrdersResolver"));
+}
+
+#[test]
+fn switching_databases_keeps_the_registered_entities() {
+    let config = DefaultConfig::new(Api::Graphql, Routing::Spring, Language::Ts, Database::MySQL);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    add(root, Integration::Postgres).unwrap();
+
+    let data_source = fs::read_to_string(root.join("src/shared/configs/data_source.ts")).unwrap();
+    assert!(data_source.contains("type: \"postgres\""));
+    assert!(data_source.contains("entities: [UserProfile]"));
+    assert!(data_source.contains("import { UserProfile } from"));
+}
+
+#[test]
+fn existing_docker_files_are_kept() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+    fs::write(root.join("Dockerfile"), "FROM node:22\n").unwrap();
+    fs::remove_file(root.join(".dockerignore")).unwrap();
+
+    add(root, Integration::Docker).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(root.join("Dockerfile")).unwrap(),
+        "FROM node:22\n"
+    );
+    assert!(root.join(".dockerignore").exists());
+}
@@ -0,0 +1,290 @@
+//! How the generator combines writers, beyond the files of a single schematic.
+
+use std::fs;
+use std::path::Path;
+
+use crate::config::{Api, Database, DefaultConfig, Language, Routing};
+use crate::error::DolphError;
+use crate::init::scaffold_project;
+use crate::output::FileAction;
+use crate::project::Project;
+use crate::properties::{Generator, Schematic};
+use crate::writers::MergePolicy;
+
+use super::{combinations, describe, files, generate_project};
+
+#[test]
+fn schematics_match_the_config() {
+    for config in combinations() {
+        let available = Schematic::available(&config);
+
+        assert!(available.contains(&Schematic::All));
+        assert_eq!(
+            available.contains(&Schematic::Resolver),
+            config.api == Api::Graphql
+        );
+        assert_eq!(
+            available.contains(&Schematic::Component),
+            config.api == Api::Rest && config.routing == Routing::Spring
+        );
+    }
+}
+
+#[test]
+fn registers_only_generated_files() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    let generator = Generator::new(Project::new(root, config)).with_register(true);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(generator.generate(Schematic::Controller, "orders"))
+        .unwrap();
+
+    let component =
+        fs::read_to_string(root.join("src/components/orders/orders.component.ts")).unwrap();
+    assert!(component.contains("controllers: [OrdersController], services: []"));
+    assert!(!component.contains("orders.service"));
+
+    let server = fs::read_to_string(root.join("src/server.ts")).unwrap();
+    assert!(server.contains("./components/orders/orders.component"));
+}
+
+#[test]
+fn server_registration_keeps_other_components() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    let generator = Generator::new(Project::new(root, config)).with_register(true);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            generator.generate(Schematic::Component, "orders").await?;
+            generator.generate(Schematic::Component, "orders").await?;
+            generator.generate(Schematic::Socket, "chat").await
+        })
+        .unwrap();
+
+    let server = fs::read_to_string(root.join("src/server.ts")).unwrap();
+    assert!(server.contains(
+        "new DolphFactory([UserProfileComponent, OrdersComponent], ChatSocketComponent)"
+    ));
+    assert_eq!(server.matches("OrdersComponent }").count(), 1);
+    assert!(!server.contains("UserProfileSocketComponent)"));
+}
+
+#[test]
+fn graphql_resources_are_registered() {
+    let config = DefaultConfig::new(Api::Graphql, Routing::Spring, Language::Ts, Database::MySQL);
+    let project = generate_project(&config, true);
+    let root = project.root.path();
+
+    let generator = Generator::new(Project::new(root, config));
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            generator.generate(Schematic::All, "orders").await?;
+            generator.generate(Schematic::Entity, "orders").await
+        })
+        .unwrap();
+
+    let data_source = fs::read_to_string(root.join("src/shared/configs/data_source.ts")).unwrap();
+    assert!(data_source.contains("entities: [UserProfile, Orders]"));
+    assert!(data_source
+        .contains("import { Orders } from \"../../components/orders/entities/orders.entity\";"));
+    assert!(data_source.contains(
+        "import { UserProfile } from \"../../components/admin/user-profile/entities/user-profile.entity\";"
+    ));
+
+    let setup = fs::read_to_string(root.join("src/setup.ts")).unwrap();
+    assert!(setup.contains("const resolvers = [UserProfileResolver, OrdersResolver]"));
+    assert!(setup.contains(
+        "import { OrdersResolver } from \"./components/orders/resolvers/orders.resolver\";"
+    ));
+}
+
+#[test]
+fn applying_to_a_missing_handler_fails() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    for target in ["orders", "admin/user-profile:findAll"] {
+        let generator = Generator::new(Project::new(root, config.clone()))
+            .with_apply_to(Some(target.to_string()));
+        let result = runtime.block_on(generator.generate(Schematic::Shield, "auth"));
+        assert!(
+            matches!(result, Err(DolphError::Usage(_))),
+            "{}: {:?}",
+            target,
+            result
+        );
+    }
+}
+
+#[test]
+fn barrels_follow_the_files_on_disk() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Express, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+    let helpers = root.join("src/helpers");
+
+    fs::write(
+        helpers.join("index.ts"),
+        "export { legacy } from \"./legacy\";\nexport * from \"./admin/format-name.helper\";\n",
+    )
+    .unwrap();
+    fs::remove_file(helpers.join("admin/format-name.helper.ts")).unwrap();
+
+    let generator = Generator::new(Project::new(root, config));
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(generator.generate(Schematic::Helper, "slugify"))
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(helpers.join("index.ts")).unwrap(),
+        "export { legacy } from \"./legacy\";\n\nexport * from \"./slugify.helper\";\n"
+    );
+}
+
+#[test]
+fn rescaffolding_keeps_the_env_file() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MySQL);
+    let project = generate_project(&config, false);
+    let root = project.root.path();
+
+    fs::write(root.join(".env"), "DB_PASSWORD=secret\n").unwrap();
+    scaffold_project(&Project::new(root, config), "app", MergePolicy::Keep).unwrap();
+
+    let env = fs::read_to_string(root.join(".env")).unwrap();
+    assert!(env.starts_with("DB_PASSWORD=secret\n"));
+    assert_eq!(env.matches("DB_PASSWORD=").count(), 1);
+    assert!(env.contains("DB_HOST=localhost\n"));
+    let example = fs::read_to_string(root.join(".env.example")).unwrap();
+    assert!(example.contains("DB_PASSWORD=\n"));
+    assert!(
+        !fs::read_to_string(root.join("src/shared/configs/db.config.ts"))
+            .unwrap()
+            .contains("\"root\"")
+    );
+}
+
+#[test]
+fn rejects_components_clashing_across_namespaces() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let project = generate_project(&config, false);
+    let before = files(project.root.path());
+
+    let generator = Generator::new(Project::new(project.root.path(), config));
+    assert!(matches!(
+        generator.resource("billing/user-profile"),
+        Err(DolphError::InvalidName { suggestion: Some(suggestion), .. })
+            if suggestion == "billing/billing-user-profile"
+    ));
+    assert!(generator.resource("admin/user-profile").is_ok());
+
+    assert_eq!(files(project.root.path()), before);
+}
+
+#[test]
+fn projects_record_their_own_files() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+    let first = generate_project(&config, false);
+    let second = generate_project(&config, false);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let generator = Generator::new(Project::new(first.root.path(), config.clone()));
+    runtime
+        .block_on(generator.generate(Schematic::Helper, "slugify"))
+        .unwrap();
+    let other = Generator::new(Project::new(second.root.path(), config));
+    runtime
+        .block_on(other.generate(Schematic::Helper, "format"))
+        .unwrap();
+
+    let changes = generator.project().changes().take();
+    let paths: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            (
+                change.path.strip_prefix(first.root.path()).unwrap(),
+                change.action,
+            )
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            (
+                Path::new("src/shared/helpers/slugify.helper.ts"),
+                FileAction::Created
+            ),
+            (
+                Path::new("src/shared/helpers/index.ts"),
+                FileAction::Modified
+            ),
+        ]
+    );
+    assert_eq!(other.project().changes().take().len(), 2);
+    assert!(generator.project().changes().take().is_empty());
+}
+
+#[test]
+fn crud_needs_a_generated_model() {
+    for database in [Database::PostgreSQL, Database::Other] {
+        let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, database);
+        let project = generate_project(&config, false);
+        let root = project.root.path();
+
+        let generator = Generator::new(Project::new(root, config)).with_crud(true);
+        let result = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(generator.generate_all("orders"));
+
+        assert!(matches!(result, Err(DolphError::Usage(_))), "{:?}", result);
+        assert!(!root.join("src/components/orders").exists());
+    }
+}
+
+#[test]
+fn entries_point_at_the_generated_server() {
+    for config in combinations() {
+        let Some(server_file) = config.server_file() else {
+            continue;
+        };
+        let project = generate_project(&config, false);
+        let root = project.root.path();
+
+        let source = Path::new("src").join(server_file);
+        assert!(root.join(&source).is_file(), "{}", describe(&config, false));
+        // `dolph build` compiles src/ into app/
+        let entry = Path::new(config.language.entry_file());
+        let expected = match config.language {
+            Language::Ts => Path::new("app").join(source.with_extension("js")),
+            Language::Js => source,
+        };
+        assert_eq!(entry, expected);
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(root.join("package.json")).unwrap()).unwrap();
+        assert_eq!(manifest["main"], config.language.entry_file());
+        let dockerfile = fs::read_to_string(root.join("Dockerfile")).unwrap();
+        assert!(dockerfile.contains(&format!(
+            "CMD [\"node\", \"{}\"]",
+            config.language.entry_file()
+        )));
+    }
+}
@@ -0,0 +1,87 @@
+//! `dolph new .` in a directory with its own package.json, tsconfig.json and .gitignore.
+
+use std::fs;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+use crate::config::{Api, Database, DefaultConfig, Language, Routing};
+use crate::init::scaffold_project;
+use crate::project::Project;
+use crate::writers::MergePolicy;
+
+fn read_json(path: &std::path::Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn scaffolding_merges_into_existing_configs() {
+    let root = TempDir::new().unwrap();
+    let root = root.path();
+    fs::write(
+        root.join("package.json"),
+        json!({
+            "name": "legacy",
+            "version": "0.3.0",
+            "scripts": { "lint": "eslint ." },
+            "dependencies": { "@dolphjs/dolph": "^2.1.0", "lodash": "^4.17.21" }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    fs::write(
+        root.join("tsconfig.json"),
+        json!({ "compilerOptions": { "strict": true } }).to_string(),
+    )
+    .unwrap();
+    fs::write(root.join(".gitignore"), "dist\nnode_modules").unwrap();
+
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MySQL);
+    scaffold_project(&Project::new(root, config), "app", MergePolicy::Keep).unwrap();
+
+    let manifest = read_json(&root.join("package.json"));
+    assert_eq!(manifest["name"], "legacy");
+    assert_eq!(manifest["version"], "0.3.0");
+    assert_eq!(manifest["scripts"]["lint"], "eslint .");
+    assert_eq!(manifest["scripts"]["build"], "dolph build");
+    assert_eq!(manifest["dependencies"]["@dolphjs/dolph"], "^2.1.0");
+    assert_eq!(manifest["dependencies"]["lodash"], "^4.17.21");
+    assert_eq!(manifest["dependencies"]["sequelize"], "^6.37.3");
+    assert_eq!(manifest["devDependencies"]["typescript"], "^5.2.2");
+
+    let tsconfig = read_json(&root.join("tsconfig.json"));
+    assert_eq!(tsconfig["compilerOptions"]["strict"], true);
+    assert_eq!(tsconfig["compilerOptions"]["outDir"], "app");
+    assert_eq!(
+        tsconfig["compilerOptions"]["paths"]["@/components/*"],
+        json!(["components/*"])
+    );
+
+    assert_eq!(
+        fs::read_to_string(root.join(".gitignore")).unwrap(),
+        "dist\nnode_modules\n.env\nlogs\nyarn-error.log\napp\n"
+    );
+}
+
+#[test]
+fn customized_values_are_replaced_as_the_merge_policy_decides() {
+    let config = DefaultConfig::new(Api::Rest, Routing::Spring, Language::Ts, Database::MongoDB);
+
+    for (merge, out_dir) in [
+        (MergePolicy::Keep, "dist"),
+        (MergePolicy::Confirm(&|_| Ok(true)), "app"),
+    ] {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        fs::write(
+            root.join("tsconfig.json"),
+            json!({ "compilerOptions": { "outDir": "dist" } }).to_string(),
+        )
+        .unwrap();
+
+        scaffold_project(&Project::new(root, config.clone()), "app", merge).unwrap();
+
+        let tsconfig = read_json(&root.join("tsconfig.json"));
+        assert_eq!(tsconfig["compilerOptions"]["outDir"], out_dir);
+    }
+}
@@ -0,0 +1,145 @@
+//! Test helpers shared by the writer tests, which generate a project for every config
+//! combination into a temporary directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+
+use crate::config::{Api, ConfigValue, Database, DefaultConfig, Language, Routing};
+use crate::error::DolphError;
+use crate::init::scaffold_project;
+use crate::project::Project;
+use crate::properties::Generator;
+use crate::utils::Field;
+use crate::writers::{MergePolicy, SharedKind};
+
+mod add;
+mod generator;
+mod merge;
+mod snapshots;
+mod well_formed;
+
+const COMPONENT_NAME: &str = "admin/user-profile";
+const FIELDS: &str = "displayName:string,age:number,verified:boolean,birthDate:date";
+
+/// A generated project, removed from disk when dropped.
+pub struct GeneratedProject {
+    pub root: TempDir,
+}
+
+/// Every config `dolph new` can produce.
+pub fn combinations() -> Vec<DefaultConfig> {
+    let mut configs = Vec::new();
+
+    for &api in Api::VARIANTS {
+        for &routing in Routing::VARIANTS {
+            for &language in Language::VARIANTS {
+                if api == Api::Graphql && (routing != Routing::Spring || language != Language::Ts) {
+                    continue;
+                }
+                for &database in Database::VARIANTS {
+                    configs.push(DefaultConfig::new(api, routing, language, database));
+                }
+            }
+        }
+    }
+
+    configs
+}
+
+pub fn describe(config: &DefaultConfig, crud: bool) -> String {
+    format!(
+        "{}-{}-{}-{}{}",
+        config.api,
+        config.routing,
+        config.language,
+        config.database,
+        if crud { "-crud" } else { "" }
+    )
+}
+
+/// The `--crud` variants generated for `config`, see [`DefaultConfig::supports_crud`].
+pub fn crud_variants(config: &DefaultConfig) -> Vec<bool> {
+    if config.supports_crud() {
+        vec![false, true]
+    } else {
+        vec![false]
+    }
+}
+
+/// Scaffolds a project for `config` and generates a component in it, with every writer that
+/// applies to the config.
+pub fn generate_project(config: &DefaultConfig, crud: bool) -> GeneratedProject {
+    let root = TempDir::new().unwrap();
+    let project = Project::new(root.path(), config.clone());
+
+    scaffold_project(&project, "app", MergePolicy::Keep).unwrap();
+
+    // CRUD projects also declare fields, so both the bare and the filled templates are covered
+    let fields = if crud {
+        Field::parse_list(FIELDS).unwrap()
+    } else {
+        Vec::new()
+    };
+    let generator = Generator::new(project).with_crud(crud).with_fields(fields);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            generator.generate_all(COMPONENT_NAME).await?;
+
+            // Writers that `generate --all` leaves out
+            if config.api == Api::Rest && config.routing == Routing::Spring {
+                generator.generate_server(COMPONENT_NAME).await?;
+                generator.generate_socket(COMPONENT_NAME).await?;
+
+                let handler = if crud { "findAll" } else { "greet" };
+                generator
+                    .clone()
+                    .with_apply_to(Some(COMPONENT_NAME.to_string()))
+                    .generate_shield("admin/auth")
+                    .await?;
+                generator
+                    .clone()
+                    .with_apply_to(Some(format!("{}:{}", COMPONENT_NAME, handler)))
+                    .generate_middleware("request-logger")
+                    .await?;
+            } else if config.api == Api::Rest {
+                generator.generate_middleware("request-logger").await?;
+            }
+
+            generator.generate_shared(SharedKind::Decorator, "log-calls")?;
+            generator.generate_shared(SharedKind::Helper, "admin/format-name")?;
+            generator.generate_shared(SharedKind::Constant, "roles")?;
+            generator.generate_shared(SharedKind::Interface, "pagination")?;
+            generator.generate_shared(SharedKind::Validation, "is-slug")?;
+
+            Ok::<_, DolphError>(())
+        })
+        .unwrap();
+
+    GeneratedProject { root }
+}
+
+/// Every file below `dir`, sorted by path.
+pub fn files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if path.file_name().is_some_and(|name| name != "node_modules") {
+                    pending.push(path);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
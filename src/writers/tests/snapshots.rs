@@ -0,0 +1,85 @@
+//! Golden-file snapshots of every generated project, one file per config combination, so a
+//! template change shows up as a reviewable diff.
+//!
+//! Run `DOLPH_UPDATE_SNAPSHOTS=1 cargo test` to rewrite the snapshots after an intended change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{combinations, crud_variants, describe, files, generate_project};
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/writers/tests/snapshots")
+}
+
+/// Concatenates every file of the project, each under a header with its relative path.
+fn render(root: &Path) -> String {
+    let mut snapshot = String::new();
+
+    for file in files(root) {
+        let content = fs::read_to_string(&file).unwrap();
+        snapshot.push_str(&format!(
+            "==> {} <==\n{}\n",
+            file.strip_prefix(root).unwrap().display(),
+            content.trim_end()
+        ));
+    }
+
+    snapshot
+}
+
+#[test]
+fn generated_projects_match_snapshots() {
+    let update = std::env::var_os("DOLPH_UPDATE_SNAPSHOTS").is_some();
+    let mut mismatches = Vec::new();
+
+    for config in combinations() {
+        for crud in crud_variants(&config) {
+            let project = generate_project(&config, crud);
+            let actual = render(project.root.path());
+            let path = snapshot_dir().join(format!("{}.snap", describe(&config, crud)));
+
+            if update {
+                fs::create_dir_all(snapshot_dir()).unwrap();
+                fs::write(&path, actual).unwrap();
+                continue;
+            }
+
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => mismatches.push(format!(
+                    "{} differs:\n{}",
+                    path.display(),
+                    first_difference(&expected, &actual)
+                )),
+                Err(_) => mismatches.push(format!("{} is missing", path.display())),
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "\n{}\n\nrun `DOLPH_UPDATE_SNAPSHOTS=1 cargo test` and review the diff if the change is intended",
+        mismatches.join("\n")
+    );
+}
+
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => line += 1,
+            (expected, actual) => {
+                return format!(
+                    "  line {}\n  - {}\n  + {}",
+                    line,
+                    expected.unwrap_or("<end of snapshot>"),
+                    actual.unwrap_or("<end of output>")
+                )
+            }
+        }
+    }
+}
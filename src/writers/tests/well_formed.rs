@@ -0,0 +1,206 @@
+//! Checks that the projects every writer produces hang together, for every config combination.
+//!
+//! Each project is scaffolded the way `dolph new` does it and then filled with `generate --all`,
+//! with and without `--crud`. Without a TypeScript toolchain, only the imports are checked:
+//! relative and `@/` imports must point at files that exist and packages must be declared in
+//! package.json. Whether the projects compile is left to `tsc`, in the ignored
+//! `generated_typescript_type_checks` test, which runs with
+//! `DOLPH_TYPECHECK_NODE_MODULES=<node_modules> cargo test -- --ignored`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Language;
+
+use super::{combinations, crud_variants, describe, files, generate_project};
+
+#[test]
+fn generated_imports_resolve() {
+    let mut problems = Vec::new();
+
+    for config in combinations() {
+        for crud in crud_variants(&config) {
+            let project = generate_project(&config, crud);
+            let root = project.root.path();
+
+            for file in files(root) {
+                if file.extension().is_none_or(|extension| extension != "ts") {
+                    continue;
+                }
+                for specifier in imported_modules(&fs::read_to_string(&file).unwrap()) {
+                    if let Some(problem) = check_import(root, &file, specifier) {
+                        problems.push(format!(
+                            "{}: {}: {}",
+                            describe(&config, crud),
+                            file.strip_prefix(root).unwrap().display(),
+                            problem
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+}
+
+#[test]
+#[ignore = "needs DOLPH_TYPECHECK_NODE_MODULES pointing at installed project dependencies"]
+fn generated_typescript_type_checks() {
+    let node_modules = std::env::var_os("DOLPH_TYPECHECK_NODE_MODULES")
+        .map(PathBuf::from)
+        .expect("DOLPH_TYPECHECK_NODE_MODULES has to point at a node_modules directory");
+    let tsc = node_modules.join(".bin").join("tsc");
+    assert!(tsc.exists(), "{} does not exist", tsc.display());
+    let mut failures = Vec::new();
+
+    for config in combinations() {
+        if config.language != Language::Ts {
+            continue;
+        }
+        for crud in crud_variants(&config) {
+            let project = generate_project(&config, crud);
+            let root = project.root.path();
+            link_node_modules(&node_modules, &root.join("node_modules"));
+
+            let output = Command::new(&tsc)
+                .args(["--noEmit", "-p", "tsconfig.json"])
+                .current_dir(root)
+                .output()
+                .unwrap();
+
+            if !output.status.success() {
+                failures.push(format!(
+                    "{}:\n{}",
+                    describe(&config, crud),
+                    String::from_utf8_lossy(&output.stdout)
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[cfg(unix)]
+fn link_node_modules(source: &Path, link: &Path) {
+    std::os::unix::fs::symlink(source, link).unwrap();
+}
+
+#[cfg(windows)]
+fn link_node_modules(source: &Path, link: &Path) {
+    std::os::windows::fs::symlink_dir(source, link).unwrap();
+}
+
+/// The modules `content` imports or re-exports. The writers put every `from "..."` at the end of a
+/// line.
+fn imported_modules(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let specifier = match line.rsplit_once(" from ") {
+                Some((_, specifier))
+                    if line.starts_with("import")
+                        || line.starts_with('}')
+                        || line.starts_with("export") =>
+                {
+                    specifier
+                }
+                _ => line.strip_prefix("import ")?,
+            };
+            let specifier = specifier.trim_end_matches(';');
+            specifier
+                .strip_prefix('"')
+                .and_then(|specifier| specifier.strip_suffix('"'))
+        })
+        .collect()
+}
+
+/// Resolves `specifier` the way tsc does, with the longest matching `compilerOptions.paths` entry.
+/// Projects without a tsconfig.json resolve `@/` to `src/`.
+fn resolve_alias(root: &Path, specifier: &str) -> Option<PathBuf> {
+    let Ok(tsconfig) = fs::read_to_string(root.join("tsconfig.json")) else {
+        return Some(root.join("src").join(specifier.strip_prefix("@/")?));
+    };
+    let tsconfig: serde_json::Value = serde_json::from_str(&tsconfig).ok()?;
+    let base_url = tsconfig
+        .pointer("/compilerOptions/baseUrl")
+        .and_then(|base_url| base_url.as_str())
+        .unwrap_or(".");
+
+    let (rest, target) = tsconfig
+        .pointer("/compilerOptions/paths")?
+        .as_object()?
+        .iter()
+        .filter_map(|(pattern, targets)| {
+            let target = targets.get(0)?.as_str()?;
+            match pattern.strip_suffix('*') {
+                Some(prefix) => Some((specifier.strip_prefix(prefix)?, target)),
+                None => (pattern == specifier).then_some(("", target)),
+            }
+        })
+        .min_by_key(|(rest, _)| rest.len())?;
+
+    Some(root.join(base_url).join(target.replacen('*', rest, 1)))
+}
+
+fn check_import(root: &Path, file: &Path, specifier: &str) -> Option<String> {
+    let target = if specifier.starts_with('.') {
+        file.parent().unwrap().join(specifier)
+    } else if specifier.starts_with("@/") {
+        match resolve_alias(root, specifier) {
+            Some(target) => target,
+            None => {
+                return Some(format!(
+                    "imports `{}`, which no path alias in tsconfig.json matches",
+                    specifier
+                ))
+            }
+        }
+    } else {
+        let package = package_name(specifier);
+        let manifest = fs::read_to_string(root.join("package.json")).unwrap_or_default();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap_or_default();
+        let declared = ["dependencies", "devDependencies"]
+            .iter()
+            .any(|section| manifest[section].get(package).is_some());
+
+        return (!declared && !is_node_builtin(package)).then(|| {
+            format!(
+                "imports `{}`, which is not declared in package.json",
+                package
+            )
+        });
+    };
+
+    let exists = [
+        PathBuf::from(format!("{}.ts", target.display())),
+        PathBuf::from(format!("{}.js", target.display())),
+        target.join("index.ts"),
+    ]
+    .iter()
+    .any(|candidate| candidate.is_file());
+
+    (!exists).then(|| format!("imports `{}`, which does not exist", specifier))
+}
+
+fn package_name(specifier: &str) -> &str {
+    let mut segments = specifier.splitn(3, '/');
+    let first = segments.next().unwrap_or_default();
+    if first.starts_with('@') {
+        let len = first.len() + segments.next().map_or(0, |second| second.len() + 1);
+        &specifier[..len]
+    } else {
+        first
+    }
+}
+
+fn is_node_builtin(package: &str) -> bool {
+    package.starts_with("node:")
+        || [
+            "fs", "path", "http", "crypto", "os", "url", "util", "events",
+        ]
+        .contains(&package)
+}